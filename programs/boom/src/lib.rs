@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::hash;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::hash::{hash, hashv};
 use anchor_lang::solana_program::instruction::AccountMeta;
 use anchor_spl::token::{Mint, Token};
 use anchor_spl::token_2022::{self, Token2022};
-use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface};
+use anchor_spl::token_interface::{self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
 use anchor_spl::associated_token::AssociatedToken;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
@@ -26,11 +27,24 @@ pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
     0x0d, 0xea, 0x09, 0x5c, 0xfc, 0x73, 0x2d, 0x83,
 ]); // DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb
 
+// Default time after end_time to start trading before a round counts as a failed launch
+pub const DEFAULT_LAUNCH_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+/// Default time holders have to claim explosion payouts before they can be swept
+pub const DEFAULT_CLAIM_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Shortest claim window a round can be configured with (0 = no deadline is also allowed)
+pub const MIN_CLAIM_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Size of the last-buyer jackpot ring buffer
+pub const MAX_JACKPOT_BUYERS: usize = 10;
+
 #[program]
 pub mod boom {
     use super::*;
 
     pub fn initialize(ctx: Context<Initialize>, config: ProtocolConfig) -> Result<()> {
+        require!(config.min_cap <= config.max_cap, BoomError::InvalidCapBounds);
         let protocol = &mut ctx.accounts.protocol;
         protocol.authority = ctx.accounts.authority.key();
         protocol.treasury = ctx.accounts.treasury.key();
@@ -55,6 +69,34 @@ pub mod boom {
         boom_token.is_exploded = false;
         boom_token.bump = ctx.bumps.boom_token;
         protocol.total_launches += 1;
+
+        let launch = &mut ctx.accounts.launch;
+        launch.init_legacy(boom_token, boom_token.key(), ctx.accounts.creator.key(), ctx.bumps.launch);
+
+        emit!(LaunchCreated {
+            mint: launch.mint,
+            kind: LaunchKind::Legacy,
+            round_id: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Create the unified Launch record for a BoomToken created before launches existed.
+    /// Protocol counters already include the token, so they are left untouched.
+    /// Optional: legacy tokens explode without a launch record, and one migrated
+    /// after the explosion is recorded as already exploded.
+    pub fn migrate_boom_token(ctx: Context<MigrateBoomToken>) -> Result<()> {
+        let boom_token = &ctx.accounts.boom_token;
+        let launch = &mut ctx.accounts.launch;
+        launch.init_legacy(boom_token, boom_token.key(), ctx.accounts.payer.key(), ctx.bumps.launch);
+
+        emit!(LaunchCreated {
+            mint: launch.mint,
+            kind: LaunchKind::Legacy,
+            round_id: 0,
+        });
+
         Ok(())
     }
 
@@ -72,11 +114,13 @@ pub mod boom {
         require!(!boom_token.is_exploded, BoomError::AlreadyExploded);
         let computed_hash = hash(&revealed_cap.to_le_bytes());
         require!(computed_hash.to_bytes() == boom_token.cap_hash, BoomError::InvalidCapReveal);
+        protocol.check_cap(revealed_cap)?;
         boom_token.is_exploded = true;
         boom_token.explosion_time = Clock::get()?.unix_timestamp;
         boom_token.revealed_cap = revealed_cap;
         boom_token.explosion_reason = ExplosionReason::CapHit;
         protocol.total_explosions += 1;
+        mark_legacy_launch_exploded(&ctx.accounts.launch, boom_token.explosion_time)?;
         
         emit!(TokenExploded {
             mint: boom_token.mint,
@@ -121,6 +165,7 @@ pub mod boom {
         boom_token.explosion_time = clock.unix_timestamp;
         boom_token.explosion_reason = ExplosionReason::TimeLimit;
         protocol.total_explosions += 1;
+        mark_legacy_launch_exploded(&ctx.accounts.launch, boom_token.explosion_time)?;
         
         emit!(TokenExploded {
             mint: boom_token.mint,
@@ -142,9 +187,32 @@ pub mod boom {
         lottery_spots: u32,
         min_deposit: u64,
         max_deposit: u64,
+        allocation: AllocationConfig,
     ) -> Result<()> {
         let presale = &mut ctx.accounts.presale_round;
         let clock = Clock::get()?;
+        // No quote mint account = native SOL round
+        let quote_mint = ctx
+            .accounts
+            .quote_mint
+            .as_ref()
+            .map(|mint| mint.key())
+            .unwrap_or_default();
+
+        // Vault balances must match the amounts credited to the round
+        if let Some(mint) = ctx.accounts.quote_mint.as_ref() {
+            require!(
+                !mint_has_transfer_fee(&mint.to_account_info())?,
+                BoomError::TransferFeeQuoteMint
+            );
+        }
+
+        if allocation.mode != AllocationMode::FixedPerWinner {
+            require!(allocation.raise_target > 0, BoomError::InvalidAllocationConfig);
+        }
+        if allocation.mode == AllocationMode::CappedProRata {
+            require!(allocation.allocation_cap > 0, BoomError::InvalidAllocationConfig);
+        }
 
         presale.authority = ctx.accounts.authority.key();
         presale.round_id = round_id;
@@ -157,6 +225,35 @@ pub mod boom {
         presale.total_depositors = 0;
         presale.is_finalized = false;
         presale.bump = ctx.bumps.presale_round;
+        presale.rent_payer = ctx.accounts.authority.key();
+        presale.allocation = allocation;
+        presale.total_capped_deposited = 0;
+        presale.winners_committed = 0;
+        presale.quote_mint = quote_mint;
+        presale.quote_authority_bump = 0;
+        presale.whitelist_root = [0u8; 32];
+        presale.withdraw_penalty_bps = 0;
+        presale.penalties_collected = 0;
+        presale.launch_grace_period = DEFAULT_LAUNCH_GRACE_PERIOD;
+        presale.launch_failed = false;
+        presale.soft_cap = 0;
+        presale.hard_cap = 0;
+        presale.anti_snipe = AntiSnipeConfig::default();
+        presale.total_extension = 0;
+        presale.vesting = VestingConfig::default();
+        presale.vesting_allocated = 0;
+        presale.vesting_minted = 0;
+        presale.settled_deposits = 0;
+        presale.stage = RoundStage::Presale;
+        presale.claim_window = DEFAULT_CLAIM_WINDOW_SECONDS;
+        presale.winner_count = 0;
+        presale.allocation_claimed = 0;
+
+        // Manually started rounds still move the sequencer to the live round
+        if let Some(sequencer) = ctx.accounts.sequencer.as_mut() {
+            require!(round_id > sequencer.current_round, BoomError::InvalidRoundSequence);
+            sequencer.current_round = round_id;
+        }
 
         emit!(PresaleStarted {
             round_id,
@@ -164,13 +261,60 @@ pub mod boom {
             lottery_spots,
             min_deposit,
             max_deposit,
+            allocation_mode: allocation.mode,
+            quote_mint,
+        });
+
+        Ok(())
+    }
+
+    /// Create the quote-token vaults for a round whose quote asset is an SPL mint
+    /// Presale deposits, pool reserves and explosion payouts each get their own
+    /// vault, all controlled by the round's quote_authority PDA.
+    pub fn init_quote_vaults(ctx: Context<InitQuoteVaults>, round_id: u64) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+        require!(presale.quote_mint != Pubkey::default(), BoomError::NativeQuoteRound);
+        presale.quote_authority_bump = ctx.bumps.quote_authority;
+
+        emit!(QuoteVaultsInitialized {
+            round_id,
+            quote_mint: presale.quote_mint,
+            presale_vault: ctx.accounts.presale_quote_vault.key(),
+            pool_vault: ctx.accounts.pool_quote_vault.key(),
+            payout_vault: ctx.accounts.payout_quote_vault.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Gate the round behind a Merkle allowlist, or swap the current root
+    /// Leaves are hash(wallet || max_deposit_le); a zero root opens the round to anyone.
+    pub fn set_whitelist_root(ctx: Context<SetWhitelistRoot>, whitelist_root: [u8; 32]) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+        let clock = Clock::get()?;
+
+        require!(!presale.is_finalized, BoomError::PresaleFinalized);
+        require!(clock.unix_timestamp < presale.end_time, BoomError::PresaleEnded);
+
+        presale.whitelist_root = whitelist_root;
+
+        emit!(WhitelistRootUpdated {
+            round_id: presale.round_id,
+            whitelist_root,
         });
 
         Ok(())
     }
 
-    /// User deposits SOL into presale
-    pub fn deposit_presale(ctx: Context<DepositPresale>, amount: u64) -> Result<()> {
+    /// User deposits the round's quote asset (SOL or SPL) into presale
+    /// Whitelisted rounds require a Merkle proof for (depositor, wallet_max_deposit);
+    /// a wallet_max_deposit of 0 means the round's max_deposit applies.
+    pub fn deposit_presale(
+        ctx: Context<DepositPresale>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        wallet_max_deposit: u64,
+    ) -> Result<()> {
         let clock = Clock::get()?;
 
         // Read presale state first (immutable)
@@ -182,24 +326,66 @@ pub mod boom {
 
         // Validate presale is active
         require!(!is_finalized, BoomError::PresaleFinalized);
+        ctx.accounts.presale_round.require_stage(&[RoundStage::Presale])?;
         require!(clock.unix_timestamp < end_time, BoomError::PresaleEnded);
         require!(amount >= min_deposit, BoomError::DepositTooSmall);
         require!(amount <= max_deposit, BoomError::DepositTooLarge);
 
         // Check if this is a new deposit or additional
-        let is_new_depositor = ctx.accounts.user_deposit.amount == 0;
-        let new_total = ctx.accounts.user_deposit.amount.checked_add(amount).ok_or(BoomError::Overflow)?;
+        let previous_total = ctx.accounts.user_deposit.amount;
+        let is_new_depositor = previous_total == 0;
+        let new_total = previous_total.checked_add(amount).ok_or(BoomError::Overflow)?;
         require!(new_total <= max_deposit, BoomError::DepositTooLarge);
 
-        // Transfer SOL to presale PDA
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.depositor.to_account_info(),
-                to: ctx.accounts.presale_round.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        // Round-level hard cap
+        let hard_cap = ctx.accounts.presale_round.hard_cap;
+        if hard_cap > 0 {
+            let round_total = ctx
+                .accounts
+                .presale_round
+                .total_deposited
+                .checked_add(amount)
+                .ok_or(BoomError::Overflow)?;
+            require!(round_total <= hard_cap, BoomError::HardCapReached);
+        }
+
+        // Allowlist check
+        let whitelist_root = ctx.accounts.presale_round.whitelist_root;
+        if whitelist_root != [0u8; 32] {
+            let leaf = hashv(&[
+                ctx.accounts.depositor.key().as_ref(),
+                &wallet_max_deposit.to_le_bytes(),
+            ])
+            .to_bytes();
+            require!(
+                verify_merkle_proof(&proof, whitelist_root, leaf),
+                BoomError::NotWhitelisted
+            );
+            if wallet_max_deposit > 0 {
+                require!(new_total <= wallet_max_deposit, BoomError::DepositTooLarge);
+            }
+        }
+
+        if ctx.accounts.presale_round.quote_mint == Pubkey::default() {
+            // Transfer SOL to presale PDA
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.presale_round.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_context, amount)?;
+        } else {
+            // Transfer quote tokens to the round's presale quote vault
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.quote_vault,
+                &ctx.accounts.depositor_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            quote.deposit(ctx.accounts.depositor.to_account_info(), amount)?;
+        }
 
         // Update user deposit
         let user_deposit = &mut ctx.accounts.user_deposit;
@@ -209,15 +395,48 @@ pub mod boom {
         user_deposit.deposit_time = clock.unix_timestamp;
         user_deposit.is_winner = false;
         user_deposit.claimed = false;
+        user_deposit.refund_claimed = false;
         user_deposit.bump = ctx.bumps.user_deposit;
 
         // Update presale stats
         let presale = &mut ctx.accounts.presale_round;
         presale.total_deposited = presale.total_deposited.checked_add(amount).ok_or(BoomError::Overflow)?;
+        // Capped pro-rata only counts each wallet up to allocation_cap
+        let cap = presale.allocation.allocation_cap;
+        if cap > 0 {
+            let capped_increase = new_total.min(cap) - previous_total.min(cap);
+            presale.total_capped_deposited = presale
+                .total_capped_deposited
+                .checked_add(capped_increase)
+                .ok_or(BoomError::Overflow)?;
+        }
         if is_new_depositor {
             presale.total_depositors += 1;
         }
 
+        // Anti-snipe: late deposits push end_time out, up to the max total extension
+        let anti_snipe = presale.anti_snipe;
+        if anti_snipe.window_seconds > 0
+            && end_time.saturating_sub(clock.unix_timestamp) <= anti_snipe.window_seconds
+        {
+            let extension = anti_snipe
+                .extension_seconds
+                .min(anti_snipe.max_total_extension.saturating_sub(presale.total_extension));
+            if extension > 0 {
+                presale.end_time = presale.end_time.checked_add(extension).ok_or(BoomError::Overflow)?;
+                presale.total_extension = presale
+                    .total_extension
+                    .checked_add(extension)
+                    .ok_or(BoomError::Overflow)?;
+
+                emit!(PresaleExtended {
+                    round_id,
+                    new_end_time: presale.end_time,
+                    total_extension: presale.total_extension,
+                });
+            }
+        }
+
         emit!(DepositMade {
             round_id,
             depositor: ctx.accounts.depositor.key(),
@@ -234,41 +453,55 @@ pub mod boom {
         winner_indexes: Vec<u32>,
     ) -> Result<()> {
         let presale = &mut ctx.accounts.presale_round;
-        let clock = Clock::get()?;
-
-        // Validate
-        require!(!presale.is_finalized, BoomError::PresaleFinalized);
-        require!(clock.unix_timestamp >= presale.end_time, BoomError::PresaleNotEnded);
         require!(
             winner_indexes.len() <= presale.lottery_spots as usize,
             BoomError::TooManyWinners
         );
 
-        presale.is_finalized = true;
-
         // Note: In production, use Switchboard VRF or similar
         // For hackathon, authority provides winner indexes based on off-chain randomness
         // using recent slot hash: Clock::get()?.slot combined with depositor list
+        finalize_presale(presale, winner_indexes.len() as u32, Clock::get()?.unix_timestamp)
+    }
 
-        emit!(PresaleFinalized {
-            round_id: presale.round_id,
-            total_deposited: presale.total_deposited,
-            total_depositors: presale.total_depositors,
-            winners_count: winner_indexes.len() as u32,
-        });
+    /// End a presale that needs no lottery draw - anyone can call after end_time
+    /// Covers pro-rata rounds and rounds that missed their soft cap.
+    pub fn end_presale(ctx: Context<EndPresale>) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            presale.allocation.mode != AllocationMode::FixedPerWinner || presale.soft_cap_missed(now),
+            BoomError::LotteryDrawRequired
+        );
 
-        Ok(())
+        finalize_presale(presale, 0, now)
     }
 
     /// Mark a user as winner (called by authority after lottery)
+    /// The presale token must be registered so the winner's purchase cost is known
     pub fn mark_winner(ctx: Context<MarkWinner>) -> Result<()> {
         let user_deposit = &mut ctx.accounts.user_deposit;
-        let presale = &ctx.accounts.presale_round;
+        let presale = &mut ctx.accounts.presale_round;
+        let presale_token = &ctx.accounts.presale_token;
 
         require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        require!(!presale.launch_failed, BoomError::LaunchFailed);
+        presale.require_stage(&[RoundStage::TokenCreated])?;
+        require!(
+            presale.allocation.mode == AllocationMode::FixedPerWinner,
+            BoomError::LotteryNotUsed
+        );
         require!(!user_deposit.is_winner, BoomError::AlreadyWinner);
+        require!(user_deposit.amount > 0, BoomError::ZeroAmount);
 
         user_deposit.is_winner = true;
+        // Only the purchase cost goes to the pool; any excess is refunded on claim
+        let purchase_cost = presale_token.winner_purchase_cost(user_deposit.amount)?;
+        presale.winners_committed = presale
+            .winners_committed
+            .checked_add(purchase_cost)
+            .ok_or(BoomError::Overflow)?;
+        presale.winner_count = presale.winner_count.checked_add(1).ok_or(BoomError::Overflow)?;
 
         emit!(WinnerMarked {
             round_id: presale.round_id,
@@ -279,28 +512,253 @@ pub mod boom {
         Ok(())
     }
 
-    /// Non-winners claim refund
-    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
-        let user_deposit = &mut ctx.accounts.user_deposit;
+    /// Set the early-withdraw penalty (in bps) for a round
+    /// Only allowed before anyone has deposited so depositors know the terms up front.
+    pub fn set_withdraw_penalty(ctx: Context<SetWithdrawPenalty>, penalty_bps: u16) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+
+        require!(penalty_bps <= 5000, BoomError::InvalidWithdrawPenalty);
+        require!(presale.total_depositors == 0, BoomError::DepositsAlreadyMade);
+
+        presale.withdraw_penalty_bps = penalty_bps;
+
+        Ok(())
+    }
+
+    /// Pull some or all of a deposit while the round is still open
+    /// The early-withdraw penalty stays in the round and is added to the pool's liquidity.
+    pub fn withdraw_presale_deposit(ctx: Context<WithdrawPresaleDeposit>, amount: u64) -> Result<()> {
+        let clock = Clock::get()?;
         let presale = &ctx.accounts.presale_round;
-        let explosion = &ctx.accounts.presale_explosion;
+        let previous_total = ctx.accounts.user_deposit.amount;
 
-        require!(presale.is_finalized, BoomError::PresaleNotFinalized);
-        // Trading must have started (explosion timer set) before refunds are available
-        require!(explosion.explosion_deadline > 0, BoomError::TradingNotStarted);
-        require!(!user_deposit.is_winner, BoomError::WinnerCannotRefund);
-        require!(!user_deposit.claimed, BoomError::AlreadyClaimed);
-        require!(user_deposit.amount > 0, BoomError::NothingToRefund);
+        require!(!presale.is_finalized, BoomError::PresaleFinalized);
+        presale.require_stage(&[RoundStage::Presale])?;
+        require!(clock.unix_timestamp < presale.end_time, BoomError::PresaleEnded);
+        require!(amount > 0, BoomError::ZeroAmount);
+        require!(amount <= previous_total, BoomError::WithdrawExceedsDeposit);
+
+        // Whatever stays deposited must still meet the round minimum
+        let new_total = previous_total - amount;
+        require!(
+            new_total == 0 || new_total >= presale.min_deposit,
+            BoomError::DepositTooSmall
+        );
+
+        let penalty = (amount as u128)
+            .checked_mul(presale.withdraw_penalty_bps as u128)
+            .ok_or(BoomError::Overflow)?
+            .checked_div(10000)
+            .ok_or(BoomError::Overflow)? as u64;
+        let payout = amount - penalty;
+
+        if payout > 0 {
+            if presale.quote_mint == Pubkey::default() {
+                // Transfer SOL from presale PDA back to user
+                let presale_info = ctx.accounts.presale_round.to_account_info();
+                let depositor_info = ctx.accounts.depositor.to_account_info();
+
+                **presale_info.try_borrow_mut_lamports()? -= payout;
+                **depositor_info.try_borrow_mut_lamports()? += payout;
+            } else {
+                // Transfer quote tokens from the presale quote vault back to user
+                let quote = SplQuote::resolve(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.quote_vault,
+                    &ctx.accounts.depositor_quote_account,
+                    &ctx.accounts.quote_token_program,
+                )?;
+                quote.withdraw(
+                    quote_authority_info(&ctx.accounts.quote_authority)?,
+                    presale.round_id,
+                    presale.quote_authority_bump,
+                    payout,
+                )?;
+            }
+        }
+
+        ctx.accounts.user_deposit.amount = new_total;
+
+        // Keep round totals consistent
+        let presale = &mut ctx.accounts.presale_round;
+        presale.total_deposited = presale.total_deposited.checked_sub(amount).ok_or(BoomError::Overflow)?;
+        let cap = presale.allocation.allocation_cap;
+        if cap > 0 {
+            let capped_decrease = previous_total.min(cap) - new_total.min(cap);
+            presale.total_capped_deposited = presale
+                .total_capped_deposited
+                .checked_sub(capped_decrease)
+                .ok_or(BoomError::Overflow)?;
+        }
+        if new_total == 0 {
+            presale.total_depositors = presale.total_depositors.saturating_sub(1);
+        }
+        presale.penalties_collected = presale
+            .penalties_collected
+            .checked_add(penalty)
+            .ok_or(BoomError::Overflow)?;
+
+        emit!(DepositWithdrawn {
+            round_id: presale.round_id,
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+            penalty,
+            remaining: new_total,
+        });
+
+        Ok(())
+    }
+
+    /// Set the round-level soft and hard caps (0 = no cap)
+    pub fn set_raise_caps(ctx: Context<SetRaiseCaps>, soft_cap: u64, hard_cap: u64) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+
+        require!(hard_cap == 0 || soft_cap <= hard_cap, BoomError::InvalidRaiseCaps);
+        require!(presale.total_depositors == 0, BoomError::DepositsAlreadyMade);
+
+        presale.soft_cap = soft_cap;
+        presale.hard_cap = hard_cap;
+
+        emit!(RaiseCapsSet {
+            round_id: presale.round_id,
+            soft_cap,
+            hard_cap,
+        });
+
+        Ok(())
+    }
+
+    /// Configure the anti-snipe end_time extension for a round
+    pub fn set_anti_snipe(ctx: Context<SetAntiSnipe>, anti_snipe: AntiSnipeConfig) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+
+        require!(
+            anti_snipe.window_seconds >= 0
+                && anti_snipe.extension_seconds >= 0
+                && anti_snipe.max_total_extension >= 0,
+            BoomError::InvalidDuration
+        );
+        require!(presale.total_depositors == 0, BoomError::DepositsAlreadyMade);
+
+        presale.anti_snipe = anti_snipe;
+
+        emit!(AntiSnipeSet {
+            round_id: presale.round_id,
+            window_seconds: anti_snipe.window_seconds,
+            extension_seconds: anti_snipe.extension_seconds,
+            max_total_extension: anti_snipe.max_total_extension,
+        });
+
+        Ok(())
+    }
+
+    /// Configure cliff + linear vesting for the round's winner allocations
+    pub fn set_vesting(ctx: Context<SetVesting>, vesting: VestingConfig) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+
+        require!(
+            vesting.vesting_seconds >= 0
+                && vesting.cliff_seconds >= 0
+                && vesting.cliff_seconds <= vesting.vesting_seconds,
+            BoomError::InvalidVestingConfig
+        );
+        require!(presale.total_depositors == 0, BoomError::DepositsAlreadyMade);
+
+        presale.vesting = vesting;
+
+        Ok(())
+    }
+
+    /// Set how long after end_time trading may start before the launch counts as failed
+    pub fn set_launch_grace_period(ctx: Context<SetLaunchGracePeriod>, grace_period: i64) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+
+        require!(grace_period > 0, BoomError::InvalidDuration);
+        require!(presale.total_depositors == 0, BoomError::DepositsAlreadyMade);
+
+        presale.launch_grace_period = grace_period;
+
+        Ok(())
+    }
+
+    /// Set how long holders can claim explosion payouts before the remainder can be swept
+    /// Fixed before deposits so the authority cannot shorten it after the explosion.
+    pub fn set_claim_window(ctx: Context<SetClaimWindow>, claim_window: i64) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+
+        validate_claim_window(claim_window)?;
+        require!(presale.total_depositors == 0, BoomError::DepositsAlreadyMade);
 
+        presale.claim_window = claim_window;
+
+        Ok(())
+    }
+
+    /// Full refund for every depositor (winners included) when the launch failed
+    /// A launch fails when no pool went live within the grace period after end_time.
+    /// Permissionless - no authority action is needed.
+    pub fn claim_failed_launch_refund(ctx: Context<ClaimFailedLaunchRefund>) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // Timer started? The explosion account may never have been created.
+        let timer_started = load_presale_explosion(&ctx.accounts.presale_explosion)?
+            .is_some_and(|explosion| explosion.explosion_deadline > 0);
+
+        let presale = &ctx.accounts.presale_round;
+        require!(!timer_started, BoomError::LaunchNotFailed);
+        require!(
+            presale.is_failed(clock.unix_timestamp),
+            BoomError::LaunchNotFailed
+        );
+
+        let user_deposit = &mut ctx.accounts.user_deposit;
+        require!(user_deposit.amount > 0, BoomError::NothingToRefund);
+        require!(
+            !user_deposit.claimed && !user_deposit.refund_claimed,
+            BoomError::AlreadyClaimed
+        );
         let refund_amount = user_deposit.amount;
         user_deposit.claimed = true;
+        user_deposit.refund_claimed = true;
+
+        if presale.quote_mint == Pubkey::default() {
+            // Transfer SOL from presale PDA back to user
+            let presale_info = ctx.accounts.presale_round.to_account_info();
+            let depositor_info = ctx.accounts.depositor.to_account_info();
+            let presale_rent = Rent::get()?.minimum_balance(presale_info.data_len());
+            require!(
+                presale_info.lamports().saturating_sub(presale_rent) >= refund_amount,
+                BoomError::InsufficientRefundFunds
+            );
 
-        // Transfer SOL from presale PDA back to user
-        let presale_info = ctx.accounts.presale_round.to_account_info();
-        let depositor_info = ctx.accounts.depositor.to_account_info();
+            **presale_info.try_borrow_mut_lamports()? -= refund_amount;
+            **depositor_info.try_borrow_mut_lamports()? += refund_amount;
+        } else {
+            // Transfer quote tokens from the presale quote vault back to user
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.quote_vault,
+                &ctx.accounts.depositor_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            require!(quote.vault.amount >= refund_amount, BoomError::InsufficientRefundFunds);
+            quote.withdraw(
+                quote_authority_info(&ctx.accounts.quote_authority)?,
+                presale.round_id,
+                presale.quote_authority_bump,
+                refund_amount,
+            )?;
+        }
 
-        **presale_info.try_borrow_mut_lamports()? -= refund_amount;
-        **depositor_info.try_borrow_mut_lamports()? += refund_amount;
+        let presale = &mut ctx.accounts.presale_round;
+        if !presale.launch_failed {
+            presale.launch_failed = true;
+            emit!(LaunchFailed {
+                round_id: presale.round_id,
+                end_time: presale.end_time,
+                grace_period: presale.launch_grace_period,
+            });
+        }
 
         emit!(RefundClaimed {
             round_id: presale.round_id,
@@ -311,24 +769,26 @@ pub mod boom {
         Ok(())
     }
 
-    /// Winners claim their token allocation - mints tokens to winner's account
-    /// Can only claim once trading has started (explosion timer set)
-    pub fn claim_winner_tokens(ctx: Context<ClaimWinnerTokens>) -> Result<()> {
-        let user_deposit = &mut ctx.accounts.user_deposit;
+    /// Mint the unlocked portion of a vesting winner allocation
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
         let presale = &ctx.accounts.presale_round;
-        let presale_token = &ctx.accounts.presale_token;
         let explosion = &ctx.accounts.presale_explosion;
+        let vesting = &mut ctx.accounts.winner_vesting;
 
-        require!(presale.is_finalized, BoomError::PresaleNotFinalized);
-        // Trading must have started (explosion timer set) before winners can claim
-        require!(explosion.explosion_deadline > 0, BoomError::TradingNotStarted);
-        require!(user_deposit.is_winner, BoomError::NotAWinner);
-        require!(!user_deposit.claimed, BoomError::AlreadyClaimed);
+        // Unvested tokens left out of the payout supply are forfeited at explosion
+        require!(
+            !explosion.is_exploded || presale.vesting.unvested_counts_toward_supply,
+            BoomError::VestingForfeited
+        );
 
-        // Calculate tokens to mint (tokens_per_winner from presale_token)
-        let tokens_to_mint = presale_token.tokens_per_winner;
+        let unlocked = presale.vesting.unlocked_amount(
+            vesting.total_amount,
+            vesting.start_time,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let claimable = unlocked.saturating_sub(vesting.claimed_amount);
+        require!(claimable > 0, BoomError::NothingVested);
 
-        // Mint tokens to winner's token account
         let round_id_bytes = presale.round_id.to_le_bytes();
         let seeds = &[
             b"mint_authority".as_ref(),
@@ -342,83 +802,408 @@ pub mod boom {
             to: ctx.accounts.winner_token_account.to_account_info(),
             authority: ctx.accounts.mint_authority.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::mint_to(cpi_ctx, claimable)?;
 
-        token_2022::mint_to(cpi_ctx, tokens_to_mint)?;
+        vesting.claimed_amount = unlocked;
 
-        user_deposit.claimed = true;
+        let presale = &mut ctx.accounts.presale_round;
+        presale.vesting_minted = presale
+            .vesting_minted
+            .checked_add(claimable)
+            .ok_or(BoomError::Overflow)?;
 
-        emit!(WinnerClaimed {
+        emit!(VestedClaimed {
             round_id: presale.round_id,
             winner: ctx.accounts.winner.key(),
-            deposit_amount: user_deposit.amount,
-            tokens_minted: tokens_to_mint,
+            amount: claimable,
+            claimed_total: unlocked,
+            total_amount: vesting.total_amount,
         });
 
         Ok(())
     }
 
-    /// Create a Token2022 mint for a finalized presale round
-    pub fn create_presale_token(
-        ctx: Context<CreatePresaleToken>,
-        round_id: u64,
-        _name: String,
-        _symbol: String,
-        total_supply: u64,
-        tokens_per_winner: u64,
-    ) -> Result<()> {
+    /// Non-winners claim refund
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let user_deposit = &mut ctx.accounts.user_deposit;
         let presale = &ctx.accounts.presale_round;
+        let now = Clock::get()?.unix_timestamp;
 
-        // Validate presale is finalized
         require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        // The pool must hold the winners' SOL (or the round failed) before refunds are available
+        require!(presale.refunds_open(now), BoomError::TradingNotStarted);
+        require!(user_deposit.amount > 0, BoomError::NothingToRefund);
 
-        // Initialize the Token2022 mint
-        // Mint authority is the mint_authority PDA which the program controls
-        let cpi_accounts = token_2022::InitializeMint2 {
-            mint: ctx.accounts.mint.to_account_info(),
+        let refund_amount = if presale.is_failed(now) {
+            // Nothing was committed - the whole deposit comes back
+            require!(
+                !user_deposit.claimed && !user_deposit.refund_claimed,
+                BoomError::AlreadyClaimed
+            );
+            user_deposit.claimed = true;
+            user_deposit.refund_claimed = true;
+            user_deposit.amount
+        } else if presale.allocation.mode == AllocationMode::FixedPerWinner {
+            require!(!user_deposit.is_winner, BoomError::WinnerCannotRefund);
+            require!(!user_deposit.claimed, BoomError::AlreadyClaimed);
+            user_deposit.claimed = true;
+            user_deposit.amount
+        } else {
+            // Pro-rata: refund the unfilled remainder, tokens are claimed separately
+            require!(!user_deposit.refund_claimed, BoomError::AlreadyClaimed);
+            let unfilled = presale.unfilled_amount(user_deposit.amount)?;
+            require!(unfilled > 0, BoomError::NothingToRefund);
+            user_deposit.refund_claimed = true;
+            unfilled
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        // 9 decimals is standard for Solana tokens
-        token_2022::initialize_mint2(
-            cpi_ctx,
-            9, // decimals
-            &ctx.accounts.mint_authority.key(),
-            Some(&ctx.accounts.mint_authority.key()),
-        )?;
+        if presale.quote_mint == Pubkey::default() {
+            // Transfer SOL from presale PDA back to user
+            let presale_info = ctx.accounts.presale_round.to_account_info();
+            let depositor_info = ctx.accounts.depositor.to_account_info();
 
-        // Initialize presale token state
-        let presale_token = &mut ctx.accounts.presale_token;
-        presale_token.round_id = round_id;
-        presale_token.mint = ctx.accounts.mint.key();
-        presale_token.total_supply = total_supply;
-        presale_token.tokens_per_winner = tokens_per_winner;
-        presale_token.bump = ctx.bumps.presale_token;
+            **presale_info.try_borrow_mut_lamports()? -= refund_amount;
+            **depositor_info.try_borrow_mut_lamports()? += refund_amount;
+        } else {
+            // Transfer quote tokens from the presale quote vault back to user
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.quote_vault,
+                &ctx.accounts.depositor_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            quote.withdraw(
+                quote_authority_info(&ctx.accounts.quote_authority)?,
+                presale.round_id,
+                presale.quote_authority_bump,
+                refund_amount,
+            )?;
+        }
 
-        emit!(PresaleTokenCreated {
-            round_id,
-            mint: ctx.accounts.mint.key(),
-            total_supply,
-            tokens_per_winner,
+        emit!(RefundClaimed {
+            round_id: presale.round_id,
+            depositor: ctx.accounts.depositor.key(),
+            amount: refund_amount,
         });
 
         Ok(())
     }
 
-    /// Register an externally-created Token2022 mint (e.g., with transfer hook)
+    /// Winners claim their token allocation - mints tokens to winner's account
+    /// Can only claim once trading has started (explosion timer set).
+    /// After the explosion the allocation is paid as a payout share instead of minted,
+    /// once the LP is unwound and while the claim window is open.
+    pub fn claim_winner_tokens(ctx: Context<ClaimWinnerTokens>) -> Result<()> {
+        let user_deposit = &mut ctx.accounts.user_deposit;
+        let presale = &ctx.accounts.presale_round;
+        let presale_token = &ctx.accounts.presale_token;
+        let explosion = &ctx.accounts.presale_explosion;
+
+        require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        // Trading must have started (explosion timer set) before winners can claim
+        require!(explosion.explosion_deadline > 0, BoomError::TradingNotStarted);
+        require!(!user_deposit.claimed, BoomError::AlreadyClaimed);
+        // No new supply after explosion - payouts are fixed against the unwound supply,
+        // which counts unclaimed allocations so late winners still get their share.
+        let exploded = explosion.is_exploded;
+        let now = Clock::get()?.unix_timestamp;
+
+        let (tokens_to_mint, purchase_cost, refund_amount) = if presale.allocation.mode == AllocationMode::FixedPerWinner {
+            // Flat tokens_per_winner for each lottery winner, paid at the launch price.
+            // Whatever was deposited above that cost is refunded.
+            require!(user_deposit.is_winner, BoomError::NotAWinner);
+            let purchase_cost = presale_token.winner_purchase_cost(user_deposit.amount)?;
+            let refund_amount = user_deposit.amount - purchase_cost;
+            (presale_token.tokens_per_winner, purchase_cost, refund_amount)
+        } else {
+            // Pro-rata: every depositor receives tokens for the filled part of their deposit.
+            // The round allocation (tokens_per_winner * lottery_spots) is priced at raise_target.
+            let filled = user_deposit
+                .amount
+                .checked_sub(presale.unfilled_amount(user_deposit.amount)?)
+                .ok_or(BoomError::Overflow)?;
+            let round_allocation = (presale_token.tokens_per_winner as u128)
+                .checked_mul(presale.lottery_spots as u128)
+                .ok_or(BoomError::Overflow)?;
+            let tokens = (filled as u128)
+                .checked_mul(round_allocation)
+                .ok_or(BoomError::Overflow)?
+                .checked_div(presale.allocation.raise_target as u128)
+                .ok_or(BoomError::Overflow)?;
+            // Unfilled remainder is refunded separately via claim_refund
+            (u64::try_from(tokens).map_err(|_| BoomError::Overflow)?, filled, 0)
+        };
+        require!(tokens_to_mint > 0, BoomError::NotAWinner);
+        let allocation = if exploded { 0 } else { tokens_to_mint };
+
+        // Late claim: the allocation takes its payout share like a holder's tokens would
+        let payout_amount = if exploded {
+            let payout_pool = ctx.accounts.payout_pool.as_mut().ok_or(BoomError::LpNotUnwound)?;
+            if payout_pool.swept || payout_pool.claim_window_closed(now) {
+                0
+            } else {
+                let amount = payout_pool.payout_for(tokens_to_mint)?;
+                payout_pool.record_claim(amount, tokens_to_mint)?;
+                amount
+            }
+        } else {
+            0
+        };
+
+        // Vesting rounds record the allocation and only mint what has unlocked
+        let tokens_to_mint = if presale.vesting.is_enabled() && !exploded {
+            let vesting = ctx
+                .accounts
+                .winner_vesting
+                .as_mut()
+                .ok_or(BoomError::MissingVestingAccount)?;
+            let start_time = explosion.timer_started_at;
+            let unlocked = presale
+                .vesting
+                .unlocked_amount(allocation, start_time, now)?;
+            vesting.round_id = presale.round_id;
+            vesting.winner = ctx.accounts.winner.key();
+            vesting.total_amount = allocation;
+            vesting.claimed_amount = unlocked;
+            vesting.start_time = start_time;
+            vesting.bump = ctx.bumps.winner_vesting.ok_or(BoomError::MissingVestingAccount)?;
+            unlocked
+        } else {
+            allocation
+        };
+
+        // Mint tokens to winner's token account
+        if tokens_to_mint > 0 {
+            let round_id_bytes = presale.round_id.to_le_bytes();
+            let seeds = &[
+                b"mint_authority".as_ref(),
+                round_id_bytes.as_ref(),
+                &[ctx.bumps.mint_authority],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_accounts = token_2022::MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.winner_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+            token_2022::mint_to(cpi_ctx, tokens_to_mint)?;
+        }
+
+        // Refund the part of the deposit above the purchase cost
+        if refund_amount > 0 {
+            if presale.quote_mint == Pubkey::default() {
+                let presale_info = ctx.accounts.presale_round.to_account_info();
+                let winner_info = ctx.accounts.winner.to_account_info();
+
+                **presale_info.try_borrow_mut_lamports()? -= refund_amount;
+                **winner_info.try_borrow_mut_lamports()? += refund_amount;
+            } else {
+                let quote = SplQuote::resolve(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.quote_vault,
+                    &ctx.accounts.winner_quote_account,
+                    &ctx.accounts.quote_token_program,
+                )?;
+                quote.withdraw(
+                    quote_authority_info(&ctx.accounts.quote_authority)?,
+                    presale.round_id,
+                    presale.quote_authority_bump,
+                    refund_amount,
+                )?;
+            }
+        }
+
+        if payout_amount > 0 {
+            if presale.quote_mint == Pubkey::default() {
+                // payout_vault is system-owned, so it can only be debited through the System Program
+                let round_id_bytes = presale.round_id.to_le_bytes();
+                let seeds = &[
+                    b"payout_vault".as_ref(),
+                    round_id_bytes.as_ref(),
+                    &[ctx.bumps.payout_vault.ok_or(BoomError::LpNotUnwound)?],
+                ];
+                let signer_seeds = &[&seeds[..]];
+
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx
+                            .accounts
+                            .payout_vault
+                            .as_ref()
+                            .ok_or(BoomError::LpNotUnwound)?
+                            .to_account_info(),
+                        to: ctx.accounts.winner.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, payout_amount)?;
+            } else {
+                let quote = SplQuote::resolve(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.payout_quote_vault,
+                    &ctx.accounts.winner_quote_account,
+                    &ctx.accounts.quote_token_program,
+                )?;
+                quote.withdraw(
+                    quote_authority_info(&ctx.accounts.quote_authority)?,
+                    presale.round_id,
+                    presale.quote_authority_bump,
+                    payout_amount,
+                )?;
+            }
+
+            emit!(PayoutClaimed {
+                round_id: presale.round_id,
+                user: ctx.accounts.winner.key(),
+                amount: payout_amount,
+            });
+        }
+
+        user_deposit.claimed = true;
+
+        if !exploded {
+            let presale = &mut ctx.accounts.presale_round;
+            presale.allocation_claimed = presale
+                .allocation_claimed
+                .checked_add(allocation)
+                .ok_or(BoomError::Overflow)?;
+            if presale.vesting.is_enabled() {
+                presale.vesting_allocated = presale
+                    .vesting_allocated
+                    .checked_add(allocation)
+                    .ok_or(BoomError::Overflow)?;
+                presale.vesting_minted = presale
+                    .vesting_minted
+                    .checked_add(tokens_to_mint)
+                    .ok_or(BoomError::Overflow)?;
+            }
+        }
+        let presale = &ctx.accounts.presale_round;
+
+        emit!(WinnerClaimed {
+            round_id: presale.round_id,
+            winner: ctx.accounts.winner.key(),
+            deposit_amount: user_deposit.amount,
+            tokens_minted: tokens_to_mint,
+            purchase_cost,
+            refund_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Create a Token2022 mint for a finalized presale round
+    pub fn create_presale_token(
+        ctx: Context<CreatePresaleToken>,
+        round_id: u64,
+        _name: String,
+        _symbol: String,
+        total_supply: u64,
+        tokens_per_winner: u64,
+        launch_price: u64,
+    ) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+
+        // Validate presale is finalized
+        require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        presale.advance_stage(&[RoundStage::Lottery], RoundStage::TokenCreated)?;
+
+        // Optional PermanentDelegate extension so crank_payouts can burn holder
+        // tokens after explosion. The mint account must be allocated with room for it.
+        if let Some(payout_delegate) = &ctx.accounts.payout_delegate {
+            let ix = spl_token_2022::instruction::initialize_permanent_delegate(
+                ctx.accounts.token_program.key,
+                ctx.accounts.mint.key,
+                payout_delegate.key,
+            )?;
+            anchor_lang::solana_program::program::invoke(
+                &ix,
+                &[
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.mint.to_account_info(),
+                ],
+            )?;
+        }
+
+        // Initialize the Token2022 mint
+        // Mint authority is the mint_authority PDA which the program controls
+        let cpi_accounts = token_2022::InitializeMint2 {
+            mint: ctx.accounts.mint.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        // 9 decimals is standard for Solana tokens
+        token_2022::initialize_mint2(
+            cpi_ctx,
+            9, // decimals
+            &ctx.accounts.mint_authority.key(),
+            Some(&ctx.accounts.mint_authority.key()),
+        )?;
+
+        // Initialize presale token state
+        let presale_token = &mut ctx.accounts.presale_token;
+        presale_token.round_id = round_id;
+        presale_token.mint = ctx.accounts.mint.key();
+        presale_token.total_supply = total_supply;
+        presale_token.tokens_per_winner = tokens_per_winner;
+        presale_token.bump = ctx.bumps.presale_token;
+        presale_token.rent_payer = ctx.accounts.authority.key();
+        presale_token.launch_price = launch_price;
+
+        // One launch record per mint, counted in the protocol stats
+        ctx.accounts.launch.init_presale(
+            ctx.accounts.mint.key(),
+            round_id,
+            ctx.accounts.presale_round.key(),
+            ctx.accounts.presale_token.key(),
+            ctx.accounts.authority.key(),
+            ctx.bumps.launch,
+        )?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.total_launches = protocol.total_launches.checked_add(1).ok_or(BoomError::Overflow)?;
+
+        emit!(LaunchCreated {
+            mint: ctx.accounts.mint.key(),
+            kind: LaunchKind::Presale,
+            round_id,
+        });
+
+        emit!(PresaleTokenCreated {
+            round_id,
+            mint: ctx.accounts.mint.key(),
+            total_supply,
+            tokens_per_winner,
+            launch_price,
+        });
+
+        Ok(())
+    }
+
+    /// Register an externally-created Token2022 mint (e.g., with transfer hook)
     /// Use this when the mint is created via script with extensions
     pub fn register_presale_token(
         ctx: Context<RegisterPresaleToken>,
         round_id: u64,
         total_supply: u64,
         tokens_per_winner: u64,
+        launch_price: u64,
     ) -> Result<()> {
-        let presale = &ctx.accounts.presale_round;
+        let presale = &mut ctx.accounts.presale_round;
 
         // Validate presale is finalized
         require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        presale.advance_stage(&[RoundStage::Lottery], RoundStage::TokenCreated)?;
 
         // Initialize presale token state (mint already exists)
         let presale_token = &mut ctx.accounts.presale_token;
@@ -427,12 +1212,33 @@ pub mod boom {
         presale_token.total_supply = total_supply;
         presale_token.tokens_per_winner = tokens_per_winner;
         presale_token.bump = ctx.bumps.presale_token;
+        presale_token.rent_payer = ctx.accounts.authority.key();
+        presale_token.launch_price = launch_price;
+
+        // One launch record per mint, counted in the protocol stats
+        ctx.accounts.launch.init_presale(
+            ctx.accounts.mint.key(),
+            round_id,
+            ctx.accounts.presale_round.key(),
+            ctx.accounts.presale_token.key(),
+            ctx.accounts.authority.key(),
+            ctx.bumps.launch,
+        )?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.total_launches = protocol.total_launches.checked_add(1).ok_or(BoomError::Overflow)?;
+
+        emit!(LaunchCreated {
+            mint: ctx.accounts.mint.key(),
+            kind: LaunchKind::Presale,
+            round_id,
+        });
 
         emit!(PresaleTokenCreated {
             round_id,
             mint: ctx.accounts.mint.key(),
             total_supply,
             tokens_per_winner,
+            launch_price,
         });
 
         Ok(())
@@ -451,8 +1257,13 @@ pub mod boom {
         vault_a: Pubkey,
         vault_b: Pubkey,
     ) -> Result<()> {
-        let presale = &ctx.accounts.presale_round;
+        let presale = &mut ctx.accounts.presale_round;
         require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        // An AMM pool may already have moved the round to PoolLive
+        presale.advance_stage(
+            &[RoundStage::TokenCreated, RoundStage::PoolLive],
+            RoundStage::PoolLive,
+        )?;
 
         let lp_info = &mut ctx.accounts.lp_info;
         lp_info.round_id = round_id;
@@ -462,6 +1273,7 @@ pub mod boom {
         lp_info.vault_b = vault_b;
         lp_info.registered_at = Clock::get()?.unix_timestamp;
         lp_info.bump = ctx.bumps.lp_info;
+        lp_info.rent_payer = ctx.accounts.authority.key();
 
         emit!(LpRegistered {
             round_id,
@@ -480,32 +1292,83 @@ pub mod boom {
         ctx: Context<CreatePool>,
         round_id: u64,
         fee_bps: u16,
+        launch_protection: LaunchProtectionConfig,
+        fee_schedule: FeeScheduleConfig,
     ) -> Result<()> {
+        ctx.accounts
+            .presale_round
+            .advance_stage(&[RoundStage::TokenCreated], RoundStage::PoolLive)?;
         let presale = &ctx.accounts.presale_round;
         let presale_token = &ctx.accounts.presale_token;
         
         require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        require!(
+            !presale.is_failed(Clock::get()?.unix_timestamp),
+            BoomError::LaunchFailed
+        );
         require!(fee_bps <= 1000, BoomError::FeeTooHigh); // Max 10%
+        require!(launch_protection.duration_seconds >= 0, BoomError::InvalidDuration);
+        require!(fee_schedule.final_window_seconds >= 0, BoomError::InvalidDuration);
+        require!(
+            fee_schedule.final_window_buy_fee_bps <= 1000
+                && fee_schedule.final_window_sell_fee_bps <= 1000
+                && fee_schedule.max_fee_bps <= 1000,
+            BoomError::FeeTooHigh
+        );
+        // A cap below the base fee would lower the fee instead of bounding the surcharges
+        require!(
+            fee_schedule.max_fee_bps == 0 || fee_schedule.max_fee_bps >= fee_bps,
+            BoomError::InvalidFeeSchedule
+        );
 
         // Get SOL from presale (winners' deposits)
         // Calculate total SOL from winners only
-        let sol_for_pool = presale.total_deposited;
+        let sol_for_pool = presale.committed_sol();
         require!(sol_for_pool > 0, BoomError::NoSolForPool);
 
-        // Transfer SOL from presale PDA to pool's SOL vault
-        let presale_info = ctx.accounts.presale_round.to_account_info();
-        let sol_vault_info = ctx.accounts.sol_vault.to_account_info();
-        
-        // Keep rent-exempt minimum in presale account
-        let presale_rent = Rent::get()?.minimum_balance(presale_info.data_len());
-        let transferable_sol = presale_info
-            .lamports()
-            .checked_sub(presale_rent)
-            .ok_or(BoomError::Overflow)?;
-        
-        // Transfer SOL to pool vault
-        **presale_info.try_borrow_mut_lamports()? -= transferable_sol;
-        **sol_vault_info.try_borrow_mut_lamports()? += transferable_sol;
+        let transferable_sol = if presale.quote_mint == Pubkey::default() {
+            // Transfer SOL from presale PDA to pool's SOL vault
+            let presale_info = ctx.accounts.presale_round.to_account_info();
+            let sol_vault_info = ctx.accounts.sol_vault.to_account_info();
+
+            // Keep rent-exempt minimum in presale account, and leave refunds owed
+            // to depositors behind (non-winners / unfilled pro-rata remainders)
+            let presale_rent = Rent::get()?.minimum_balance(presale_info.data_len());
+            let transferable_sol = presale_info
+                .lamports()
+                .checked_sub(presale_rent)
+                .ok_or(BoomError::Overflow)?
+                .min(presale.committed_sol());
+
+            // Fees and unclaimed payouts carried over from the previous round may already
+            // sit in the vault (see RoundSeed)
+            let carried_over = sol_vault_info
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+
+            // Transfer SOL to pool vault
+            **presale_info.try_borrow_mut_lamports()? -= transferable_sol;
+            **sol_vault_info.try_borrow_mut_lamports()? += transferable_sol;
+            transferable_sol
+                .checked_add(carried_over)
+                .ok_or(BoomError::Overflow)?
+        } else {
+            // Move committed quote tokens from the presale vault to the pool vault
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.presale_quote_vault,
+                &ctx.accounts.pool_quote_vault,
+                &ctx.accounts.quote_token_program,
+            )?;
+            let transferable = quote.vault.amount.min(presale.committed_sol());
+            quote.withdraw(
+                quote_authority_info(&ctx.accounts.quote_authority)?,
+                presale.round_id,
+                presale.quote_authority_bump,
+                transferable,
+            )?;
+            transferable
+        };
 
         // Get token balance in token vault
         // Note: If vault was just initialized, tokens should be deposited via deposit_pool_tokens
@@ -527,8 +1390,24 @@ pub mod boom {
         pool.total_volume = 0;
         pool.total_fees = 0;
         pool.bump = ctx.bumps.pool;
+        pool.rent_payer = ctx.accounts.authority.key();
+        ctx.accounts.launch.mark_trading(pool.key());
+        pool.quote_fees = 0;
+        pool.fees_carried_over = false;
         pool.token_vault_bump = ctx.bumps.token_vault;
         pool.sol_vault_bump = ctx.bumps.sol_vault;
+        pool.mode = PoolMode::ConstantProduct;
+        pool.curve = BondingCurveConfig::default();
+        pool.curve_supply = 0;
+        pool.launch_protection = launch_protection;
+        pool.fee_schedule = fee_schedule;
+        pool.last_price = 0;
+        pool.twap_price = 0;
+        pool.twap_updated_at = 0;
+        pool.curve_seed = 0;
+        pool.recent_move_bps = 0;
+        pool.quote_mint = presale.quote_mint;
+        pool.quote_authority_bump = presale.quote_authority_bump;
 
         emit!(PoolCreated {
             round_id,
@@ -568,13 +1447,35 @@ pub mod boom {
         let hook_config_info = ctx.accounts.hook_config.to_account_info();
         let hook_whitelist_info = ctx.accounts.hook_whitelist.to_account_info();
         
+        let mut explosion = load_presale_explosion(&ctx.accounts.presale_explosion)?;
+
         // Now get mutable reference to pool
         let pool = &mut ctx.accounts.pool;
+        require!(pool.mode == PoolMode::ConstantProduct, BoomError::CurveNotGraduated);
+
+        // Anti-sniper limits during the launch protection window
+        let mut trader_state = prepare_trader_state(
+            &mut ctx.accounts.trader_state,
+            ctx.bumps.trader_state,
+            pool.round_id,
+            user_info.key(),
+        );
+        enforce_launch_protection(
+            pool,
+            explosion.as_ref(),
+            trader_state.as_deref_mut(),
+            is_buy,
+            amount_in,
+        )?;
+        if is_buy {
+            track_holder(pool, explosion.as_ref(), trader_state)?;
+        }
         
         // Constant product formula with fee:
         // output = (reserve_out * amount_in * (10000 - fee_bps)) / (reserve_in * 10000 + amount_in * (10000 - fee_bps))
         
-        let fee_factor = 10000u128 - pool.fee_bps as u128;
+        let fee_bps = effective_fee_bps(pool, explosion.as_ref(), is_buy)?;
+        let fee_factor = 10000u128 - fee_bps as u128;
         let amount_in_128 = amount_in as u128;
         
         let amount_out = if is_buy {
@@ -620,7 +1521,7 @@ pub mod boom {
 
         // Calculate fee
         let fee_amount = (amount_in as u128)
-            .checked_mul(pool.fee_bps as u128)
+            .checked_mul(fee_bps as u128)
             .ok_or(BoomError::Overflow)?
             .checked_div(10000)
             .ok_or(BoomError::Overflow)? as u64;
@@ -629,18 +1530,31 @@ pub mod boom {
         let round_id = pool.round_id;
         let pool_bump = pool.bump;
 
+        let quote_mint = pool.quote_mint;
+        let quote_authority_bump = pool.quote_authority_bump;
+
         if is_buy {
-            // User sends SOL, receives tokens
+            // User sends SOL (or quote tokens), receives tokens
             
             // 1. Transfer SOL from user to sol_vault
-            let cpi_ctx = CpiContext::new(
-                system_program_info.clone(),
-                anchor_lang::system_program::Transfer {
-                    from: user_info.clone(),
-                    to: sol_vault_info.clone(),
-                },
-            );
-            anchor_lang::system_program::transfer(cpi_ctx, amount_in)?;
+            if quote_mint == Pubkey::default() {
+                let cpi_ctx = CpiContext::new(
+                    system_program_info.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: user_info.clone(),
+                        to: sol_vault_info.clone(),
+                    },
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, amount_in)?;
+            } else {
+                let quote = SplQuote::resolve(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.pool_quote_vault,
+                    &ctx.accounts.user_quote_account,
+                    &ctx.accounts.quote_token_program,
+                )?;
+                quote.deposit(user_info.clone(), amount_in)?;
+            }
 
             // 2. Transfer tokens from token_vault to user's token account
             // Need to use pool PDA as signer
@@ -723,8 +1637,23 @@ pub mod boom {
             )?;
 
             // 2. Transfer SOL from sol_vault to user (direct lamport manipulation for PDA)
-            **sol_vault_info.try_borrow_mut_lamports()? -= amount_out;
-            **user_info.try_borrow_mut_lamports()? += amount_out;
+            if quote_mint == Pubkey::default() {
+                **sol_vault_info.try_borrow_mut_lamports()? -= amount_out;
+                **user_info.try_borrow_mut_lamports()? += amount_out;
+            } else {
+                let quote = SplQuote::resolve(
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.pool_quote_vault,
+                    &ctx.accounts.user_quote_account,
+                    &ctx.accounts.quote_token_program,
+                )?;
+                quote.withdraw(
+                    quote_authority_info(&ctx.accounts.quote_authority)?,
+                    round_id,
+                    quote_authority_bump,
+                    amount_out,
+                )?;
+            }
 
             // Update reserves
             pool.token_reserve = pool.token_reserve.checked_add(amount_in).ok_or(BoomError::Overflow)?;
@@ -732,8 +1661,22 @@ pub mod boom {
         }
 
         // Update stats
+        record_price_move(pool)?;
         pool.total_volume = pool.total_volume.checked_add(amount_in as u128).ok_or(BoomError::Overflow)?;
+        if let (true, Some(explosion)) = (is_buy, explosion.as_mut()) {
+            extend_heat_timer(explosion, amount_in)?;
+            record_jackpot_buyer(
+                explosion,
+                &mut ctx.accounts.jackpot,
+                ctx.accounts.user.key(),
+                amount_in,
+            )?;
+            store_presale_explosion(&ctx.accounts.presale_explosion, explosion)?;
+        }
         pool.total_fees = pool.total_fees.checked_add(fee_amount as u128).ok_or(BoomError::Overflow)?;
+        if is_buy {
+            pool.quote_fees = pool.quote_fees.checked_add(fee_amount).ok_or(BoomError::Overflow)?;
+        }
 
         emit!(SwapExecuted {
             round_id: pool.round_id,
@@ -742,6 +1685,7 @@ pub mod boom {
             amount_in,
             amount_out,
             fee_amount,
+            fee_bps,
             new_sol_reserve: pool.sol_reserve,
             new_token_reserve: pool.token_reserve,
         });
@@ -757,8 +1701,11 @@ pub mod boom {
         is_buy: bool,
     ) -> Result<()> {
         let pool = &ctx.accounts.pool;
+        require!(pool.mode == PoolMode::ConstantProduct, BoomError::CurveNotGraduated);
         
-        let fee_factor = 10000u128 - pool.fee_bps as u128;
+        let explosion = load_presale_explosion(&ctx.accounts.presale_explosion)?;
+        let fee_bps = effective_fee_bps(pool, explosion.as_ref(), is_buy)?;
+        let fee_factor = 10000u128 - fee_bps as u128;
         let amount_in_128 = amount_in as u128;
         
         let (reserve_in, reserve_out) = if is_buy {
@@ -802,7 +1749,7 @@ pub mod boom {
             amount_in,
             amount_out,
             price_impact_bps,
-            fee_bps: pool.fee_bps,
+            fee_bps,
         });
 
         Ok(())
@@ -847,10 +1794,19 @@ pub mod boom {
         // Update token reserve from actual vault balance
         pool.token_reserve = token_vault.amount;
         
-        // Update SOL reserve from actual vault balance
-        let sol_vault_rent = Rent::get()?.minimum_balance(0);
-        let sol_balance = sol_vault_info.lamports();
-        pool.sol_reserve = sol_balance.saturating_sub(sol_vault_rent);
+        // Update SOL (or quote token) reserve from actual vault balance
+        if pool.quote_mint == Pubkey::default() {
+            let sol_vault_rent = Rent::get()?.minimum_balance(0);
+            let sol_balance = sol_vault_info.lamports();
+            pool.sol_reserve = sol_balance.saturating_sub(sol_vault_rent);
+        } else {
+            let pool_quote_vault = ctx
+                .accounts
+                .pool_quote_vault
+                .as_ref()
+                .ok_or(BoomError::MissingQuoteAccounts)?;
+            pool.sol_reserve = pool_quote_vault.amount;
+        }
         
         msg!("Pool reserves synced: {} tokens, {} SOL", pool.token_reserve, pool.sol_reserve);
         
@@ -879,6 +1835,7 @@ pub mod boom {
 
         let pool = &mut ctx.accounts.pool;
         let token_vault = &ctx.accounts.token_vault;
+        require!(pool.mode == PoolMode::ConstantProduct, BoomError::CurveNotGraduated);
 
         // Verify user actually deposited tokens (vault balance > recorded reserve)
         let actual_vault_balance = token_vault.amount;
@@ -890,7 +1847,9 @@ pub mod boom {
         );
 
         // Calculate SOL output using constant product formula with fee
-        let fee_factor = 10000u128 - pool.fee_bps as u128;
+        let explosion = load_presale_explosion(&ctx.accounts.presale_explosion)?;
+        let fee_bps = effective_fee_bps(pool, explosion.as_ref(), false)?;
+        let fee_factor = 10000u128 - fee_bps as u128;
         let amount_in_128 = expected_tokens_in as u128;
         let reserve_in = pool.token_reserve as u128;
         let reserve_out = pool.sol_reserve as u128;
@@ -915,7 +1874,7 @@ pub mod boom {
 
         // Calculate fee for stats
         let fee_amount = (expected_tokens_in as u128)
-            .checked_mul(pool.fee_bps as u128)
+            .checked_mul(fee_bps as u128)
             .ok_or(BoomError::Overflow)?
             .checked_div(10000)
             .ok_or(BoomError::Overflow)? as u64;
@@ -924,30 +1883,47 @@ pub mod boom {
         let round_id = pool.round_id;
         let sol_vault_bump = pool.sol_vault_bump;
 
-        // Transfer SOL from sol_vault to user using CPI with PDA signer
-        let round_id_bytes = round_id.to_le_bytes();
-        let seeds = &[
-            b"sol_vault".as_ref(),
-            round_id_bytes.as_ref(),
-            &[sol_vault_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        if pool.quote_mint == Pubkey::default() {
+            // Transfer SOL from sol_vault to user using CPI with PDA signer
+            let round_id_bytes = round_id.to_le_bytes();
+            let seeds = &[
+                b"sol_vault".as_ref(),
+                round_id_bytes.as_ref(),
+                &[sol_vault_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            system_program_info,
-            anchor_lang::system_program::Transfer {
-                from: sol_vault_info,
-                to: user_info,
-            },
-            signer_seeds,
-        );
-        anchor_lang::system_program::transfer(cpi_ctx, sol_out)?;
+            let cpi_ctx = CpiContext::new_with_signer(
+                system_program_info,
+                anchor_lang::system_program::Transfer {
+                    from: sol_vault_info,
+                    to: user_info,
+                },
+                signer_seeds,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, sol_out)?;
+        } else {
+            // Transfer quote tokens from the pool quote vault to user
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.pool_quote_vault,
+                &ctx.accounts.user_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            quote.withdraw(
+                quote_authority_info(&ctx.accounts.quote_authority)?,
+                round_id,
+                pool.quote_authority_bump,
+                sol_out,
+            )?;
+        }
 
         // Update reserves (use actual vault balance to account for any rounding)
         pool.token_reserve = actual_vault_balance;
         pool.sol_reserve = pool.sol_reserve.checked_sub(sol_out).ok_or(BoomError::Overflow)?;
 
         // Update stats
+        record_price_move(pool)?;
         pool.total_volume = pool.total_volume.checked_add(expected_tokens_in as u128).ok_or(BoomError::Overflow)?;
         pool.total_fees = pool.total_fees.checked_add(fee_amount as u128).ok_or(BoomError::Overflow)?;
 
@@ -958,6 +1934,7 @@ pub mod boom {
             amount_in: expected_tokens_in,
             amount_out: sol_out,
             fee_amount,
+            fee_bps,
             new_sol_reserve: pool.sol_reserve,
             new_token_reserve: pool.token_reserve,
         });
@@ -991,10 +1968,23 @@ pub mod boom {
         let hook_config_info = ctx.accounts.hook_config.to_account_info();
         let hook_whitelist_info = ctx.accounts.hook_whitelist.to_account_info();
 
+        let mut explosion = load_presale_explosion(&ctx.accounts.presale_explosion)?;
         let pool = &mut ctx.accounts.pool;
+        require!(pool.mode == PoolMode::ConstantProduct, BoomError::CurveNotGraduated);
+
+        // Anti-sniper limits during the launch protection window
+        let mut trader_state = prepare_trader_state(
+            &mut ctx.accounts.trader_state,
+            ctx.bumps.trader_state,
+            pool.round_id,
+            user_info.key(),
+        );
+        enforce_launch_protection(pool, explosion.as_ref(), trader_state.as_deref_mut(), true, sol_in)?;
+        track_holder(pool, explosion.as_ref(), trader_state)?;
 
         // Calculate token output using constant product formula with fee
-        let fee_factor = 10000u128 - pool.fee_bps as u128;
+        let fee_bps = effective_fee_bps(pool, explosion.as_ref(), true)?;
+        let fee_factor = 10000u128 - fee_bps as u128;
         let amount_in_128 = sol_in as u128;
         let reserve_in = pool.sol_reserve as u128;
         let reserve_out = pool.token_reserve as u128;
@@ -1019,7 +2009,7 @@ pub mod boom {
 
         // Calculate fee for stats
         let fee_amount = (sol_in as u128)
-            .checked_mul(pool.fee_bps as u128)
+            .checked_mul(fee_bps as u128)
             .ok_or(BoomError::Overflow)?
             .checked_div(10000)
             .ok_or(BoomError::Overflow)? as u64;
@@ -1028,15 +2018,25 @@ pub mod boom {
         let round_id = pool.round_id;
         let pool_bump = pool.bump;
 
-        // 1. Transfer SOL from user to sol_vault
-        let cpi_ctx = CpiContext::new(
-            system_program_info,
-            anchor_lang::system_program::Transfer {
-                from: user_info.clone(),
-                to: sol_vault_info.clone(),
-            },
-        );
-        anchor_lang::system_program::transfer(cpi_ctx, sol_in)?;
+        // 1. Transfer SOL (or quote tokens) from user to the pool
+        if pool.quote_mint == Pubkey::default() {
+            let cpi_ctx = CpiContext::new(
+                system_program_info,
+                anchor_lang::system_program::Transfer {
+                    from: user_info.clone(),
+                    to: sol_vault_info.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, sol_in)?;
+        } else {
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.pool_quote_vault,
+                &ctx.accounts.user_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            quote.deposit(user_info.clone(), sol_in)?;
+        }
 
         // 2. Transfer tokens from token_vault to user using proper hook account resolution
         let round_id_bytes = round_id.to_le_bytes();
@@ -1099,8 +2099,15 @@ pub mod boom {
         pool.token_reserve = pool.token_reserve.checked_sub(tokens_out).ok_or(BoomError::Overflow)?;
 
         // Update stats
+        record_price_move(pool)?;
         pool.total_volume = pool.total_volume.checked_add(sol_in as u128).ok_or(BoomError::Overflow)?;
+        if let Some(explosion) = explosion.as_mut() {
+            extend_heat_timer(explosion, sol_in)?;
+            record_jackpot_buyer(explosion, &mut ctx.accounts.jackpot, ctx.accounts.user.key(), sol_in)?;
+            store_presale_explosion(&ctx.accounts.presale_explosion, explosion)?;
+        }
         pool.total_fees = pool.total_fees.checked_add(fee_amount as u128).ok_or(BoomError::Overflow)?;
+        pool.quote_fees = pool.quote_fees.checked_add(fee_amount).ok_or(BoomError::Overflow)?;
 
         emit!(SwapExecuted {
             round_id,
@@ -1109,6 +2116,7 @@ pub mod boom {
             amount_in: sol_in,
             amount_out: tokens_out,
             fee_amount,
+            fee_bps,
             new_sol_reserve: pool.sol_reserve,
             new_token_reserve: pool.token_reserve,
         });
@@ -1118,188 +2126,848 @@ pub mod boom {
         Ok(())
     }
 
-    // ==================== PRESALE EXPLOSION ====================
+    // ==================== BONDING CURVE ====================
 
-    /// Initialize explosion tracking for a presale token
-    /// Sets the secret cap hash. Timer is NOT started yet - call start_explosion_timer after LP creation.
-    pub fn init_presale_explosion(
-        ctx: Context<InitPresaleExplosion>,
+    /// Create a bonding-curve pool for pre-liquidity price discovery
+    /// Tokens are minted on buy and burned on sell; SOL is held in sol_vault.
+    /// The pool graduates to the constant product AMM once the SOL raised on the
+    /// curve (sol_reserve above the presale seed) reaches the graduation threshold.
+    pub fn create_bonding_pool(
+        ctx: Context<CreatePool>,
         round_id: u64,
-        cap_hash: [u8; 32],
+        fee_bps: u16,
+        curve: BondingCurveConfig,
     ) -> Result<()> {
+        ctx.accounts
+            .presale_round
+            .advance_stage(&[RoundStage::TokenCreated], RoundStage::PoolLive)?;
         let presale = &ctx.accounts.presale_round;
+
         require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        require!(
+            !presale.is_failed(Clock::get()?.unix_timestamp),
+            BoomError::LaunchFailed
+        );
+        require!(fee_bps <= 1000, BoomError::FeeTooHigh); // Max 10%
+        require!(curve.base_price > 0, BoomError::InvalidCurveConfig);
+        require!(curve.graduation_threshold > 0, BoomError::InvalidCurveConfig);
+        if curve.kind == CurveKind::Exponential {
+            require!(curve.growth_bps > 0 && curve.step_size > 0, BoomError::InvalidCurveConfig);
+        }
+        // Bonding curves are priced in lamports - SPL quote rounds use create_pool
+        require!(presale.quote_mint == Pubkey::default(), BoomError::UnsupportedQuoteMint);
 
-        let explosion = &mut ctx.accounts.presale_explosion;
-        explosion.round_id = round_id;
-        explosion.cap_hash = cap_hash;
-        explosion.revealed_cap = 0;
-        explosion.explosion_deadline = 0; // Not set until start_explosion_timer
-        explosion.is_exploded = false;
-        explosion.explosion_time = 0;
-        explosion.explosion_reason = ExplosionReason::None;
-        explosion.total_sol_for_payout = 0;
-        explosion.bump = ctx.bumps.presale_explosion;
+        // Presale SOL seeds the vault; it pairs with the curve supply at graduation
+        let presale_info = ctx.accounts.presale_round.to_account_info();
+        let sol_vault_info = ctx.accounts.sol_vault.to_account_info();
 
-        emit!(ExplosionInitialized {
-            round_id,
-            deadline: 0, // Timer not started
-        });
+        let presale_rent = Rent::get()?.minimum_balance(presale_info.data_len());
+        let transferable_sol = presale_info
+            .lamports()
+            .checked_sub(presale_rent)
+            .ok_or(BoomError::Overflow)?
+            .min(presale.committed_sol());
+
+        // Carry-over from the previous round may already sit in the vault (see RoundSeed)
+        let carried_over = sol_vault_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+
+        **presale_info.try_borrow_mut_lamports()? -= transferable_sol;
+        **sol_vault_info.try_borrow_mut_lamports()? += transferable_sol;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.round_id = round_id;
+        pool.mint = ctx.accounts.mint.key();
+        pool.token_vault = ctx.accounts.token_vault.key();
+        pool.sol_vault = ctx.accounts.sol_vault.key();
+        pool.sol_reserve = transferable_sol
+            .checked_add(carried_over)
+            .ok_or(BoomError::Overflow)?;
+        pool.curve_seed = pool.sol_reserve;
+        pool.token_reserve = 0;
+        pool.fee_bps = fee_bps;
+        pool.total_volume = 0;
+        pool.total_fees = 0;
+        pool.bump = ctx.bumps.pool;
+        pool.rent_payer = ctx.accounts.authority.key();
+        ctx.accounts.launch.mark_trading(pool.key());
+        pool.quote_fees = 0;
+        pool.fees_carried_over = false;
+        pool.token_vault_bump = ctx.bumps.token_vault;
+        pool.sol_vault_bump = ctx.bumps.sol_vault;
+        pool.mode = PoolMode::BondingCurve;
+        pool.curve = curve;
+        pool.curve_supply = 0;
+        pool.launch_protection = LaunchProtectionConfig::default();
+        pool.fee_schedule = FeeScheduleConfig::default();
+        pool.last_price = 0;
+        pool.twap_price = 0;
+        pool.twap_updated_at = 0;
+        pool.recent_move_bps = 0;
+        pool.quote_mint = Pubkey::default();
+        pool.quote_authority_bump = 0;
+
+        emit!(BondingPoolCreated {
+            round_id,
+            mint: pool.mint,
+            kind: curve.kind,
+            base_price: curve.base_price,
+            graduation_threshold: curve.graduation_threshold,
+            fee_bps,
+        });
 
         Ok(())
     }
 
-    /// Start the explosion timer - call this AFTER LP is created
-    /// Sets deadline = now + duration_seconds
-    pub fn start_explosion_timer(
-        ctx: Context<StartExplosionTimer>,
-        duration_seconds: i64,
+    /// Buy an exact amount of tokens from the bonding curve
+    /// Tokens are minted to the buyer; cost + fee is paid into sol_vault
+    pub fn bonding_curve_buy(
+        ctx: Context<BondingCurveBuy>,
+        token_amount: u64,
+        max_sol_in: u64,
     ) -> Result<()> {
-        let explosion = &mut ctx.accounts.presale_explosion;
-        let clock = Clock::get()?;
+        require!(token_amount > 0, BoomError::ZeroAmount);
+        // No new supply after explosion - payouts are fixed against the unwound supply
+        require!(!ctx.accounts.presale_explosion.is_exploded, BoomError::AlreadyExploded);
 
-        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
-        require!(explosion.explosion_deadline == 0, BoomError::DeadlineAlreadySet);
-        require!(duration_seconds > 0, BoomError::InvalidDuration);
+        let pool = &mut ctx.accounts.pool;
+        require!(pool.mode == PoolMode::BondingCurve, BoomError::CurveGraduated);
 
-        // LP must be registered before starting timer
-        // (validated by account constraint on lp_info)
+        let new_supply = pool.curve_supply.checked_add(token_amount).ok_or(BoomError::Overflow)?;
+        let cost = curve_cumulative_cost(&pool.curve, new_supply)?
+            .checked_sub(curve_cumulative_cost(&pool.curve, pool.curve_supply)?)
+            .ok_or(BoomError::Overflow)?;
+        let cost = u64::try_from(cost).map_err(|_| BoomError::Overflow)?;
+        require!(cost > 0, BoomError::ZeroOutput);
 
-        let deadline = clock.unix_timestamp + duration_seconds;
-        explosion.explosion_deadline = deadline;
+        let fee_amount = (cost as u128)
+            .checked_mul(pool.fee_bps as u128)
+            .ok_or(BoomError::Overflow)?
+            .checked_div(10000)
+            .ok_or(BoomError::Overflow)? as u64;
+        let sol_in = cost.checked_add(fee_amount).ok_or(BoomError::Overflow)?;
+        require!(sol_in <= max_sol_in, BoomError::SlippageExceeded);
 
-        emit!(TimerStarted {
-            round_id: explosion.round_id,
-            duration_seconds,
-            deadline,
+        // 1. Transfer SOL from user to sol_vault
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.sol_vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, sol_in)?;
+
+        // 2. Mint tokens to the buyer
+        let round_id_bytes = pool.round_id.to_le_bytes();
+        let seeds = &[
+            b"mint_authority".as_ref(),
+            round_id_bytes.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_2022::mint_to(cpi_ctx, token_amount)?;
+
+        pool.curve_supply = new_supply;
+        pool.sol_reserve = pool.sol_reserve.checked_add(sol_in).ok_or(BoomError::Overflow)?;
+        pool.total_volume = pool.total_volume.checked_add(sol_in as u128).ok_or(BoomError::Overflow)?;
+        pool.total_fees = pool.total_fees.checked_add(fee_amount as u128).ok_or(BoomError::Overflow)?;
+        pool.quote_fees = pool.quote_fees.checked_add(fee_amount).ok_or(BoomError::Overflow)?;
+        record_jackpot_buyer(
+            &ctx.accounts.presale_explosion,
+            &mut ctx.accounts.jackpot,
+            ctx.accounts.user.key(),
+            sol_in,
+        )?;
+
+        emit!(SwapExecuted {
+            round_id: pool.round_id,
+            user: ctx.accounts.user.key(),
+            is_buy: true,
+            amount_in: sol_in,
+            amount_out: token_amount,
+            fee_amount,
+            fee_bps: pool.fee_bps,
+            new_sol_reserve: pool.sol_reserve,
+            new_token_reserve: pool.token_reserve,
         });
 
+        // 3. Graduate to the constant product pool once the threshold is reached
+        if pool.curve_graduates() {
+            // Seed the AMM at the current curve spot price:
+            // token_reserve = sol_reserve / price_per_token
+            let spot_price = curve_spot_price(&pool.curve, pool.curve_supply)?;
+            let lp_tokens = (pool.sol_reserve as u128)
+                .checked_mul(CURVE_TOKEN_UNIT)
+                .ok_or(BoomError::Overflow)?
+                .checked_div(spot_price)
+                .ok_or(BoomError::Overflow)?;
+            let lp_tokens = u64::try_from(lp_tokens).map_err(|_| BoomError::Overflow)?;
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token_2022::mint_to(cpi_ctx, lp_tokens)?;
+
+            pool.token_reserve = lp_tokens;
+            pool.mode = PoolMode::ConstantProduct;
+
+            emit!(PoolGraduated {
+                round_id: pool.round_id,
+                curve_supply: pool.curve_supply,
+                sol_reserve: pool.sol_reserve,
+                token_reserve: pool.token_reserve,
+            });
+        }
+
         Ok(())
     }
 
-    /// Trigger explosion by revealing the secret cap
-    /// Oracle/authority calls this when market cap threshold is reached
-    pub fn trigger_presale_explosion_cap(
-        ctx: Context<TriggerPresaleExplosion>,
-        revealed_cap: u64,
+    /// Sell tokens back to the bonding curve
+    /// Tokens are burned from the seller; proceeds minus fee are paid from sol_vault
+    pub fn bonding_curve_sell(
+        ctx: Context<BondingCurveSell>,
+        token_amount: u64,
+        min_sol_out: u64,
     ) -> Result<()> {
-        let explosion = &mut ctx.accounts.presale_explosion;
-        
-        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(token_amount > 0, BoomError::ZeroAmount);
 
-        // Verify the revealed cap matches the committed hash
-        let computed_hash = hash(&revealed_cap.to_le_bytes());
-        require!(computed_hash.to_bytes() == explosion.cap_hash, BoomError::InvalidCapReveal);
+        let pool = &mut ctx.accounts.pool;
+        require!(pool.mode == PoolMode::BondingCurve, BoomError::CurveGraduated);
 
-        explosion.is_exploded = true;
-        explosion.revealed_cap = revealed_cap;
-        explosion.explosion_time = Clock::get()?.unix_timestamp;
-        explosion.explosion_reason = ExplosionReason::CapHit;
+        let new_supply = pool.curve_supply.checked_sub(token_amount).ok_or(BoomError::InsufficientLiquidity)?;
+        let proceeds = curve_cumulative_cost(&pool.curve, pool.curve_supply)?
+            .checked_sub(curve_cumulative_cost(&pool.curve, new_supply)?)
+            .ok_or(BoomError::Overflow)?;
+        let proceeds = u64::try_from(proceeds).map_err(|_| BoomError::Overflow)?;
 
-        emit!(PresaleExplosionTriggered {
-            round_id: explosion.round_id,
-            reason: ExplosionReason::CapHit,
-            revealed_cap: Some(revealed_cap),
+        let fee_amount = (proceeds as u128)
+            .checked_mul(pool.fee_bps as u128)
+            .ok_or(BoomError::Overflow)?
+            .checked_div(10000)
+            .ok_or(BoomError::Overflow)? as u64;
+        let sol_out = proceeds.checked_sub(fee_amount).ok_or(BoomError::Overflow)?;
+
+        require!(sol_out >= min_sol_out, BoomError::SlippageExceeded);
+        require!(sol_out > 0, BoomError::ZeroOutput);
+        require!(sol_out <= pool.sol_reserve, BoomError::InsufficientLiquidity);
+
+        // 1. Burn the seller's tokens
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token_2022::burn(cpi_ctx, token_amount)?;
+
+        // 2. Transfer SOL from sol_vault to user using PDA signer
+        let round_id_bytes = pool.round_id.to_le_bytes();
+        let seeds = &[
+            b"sol_vault".as_ref(),
+            round_id_bytes.as_ref(),
+            &[pool.sol_vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                to: ctx.accounts.user.to_account_info(),
+            },
+            signer_seeds,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, sol_out)?;
+
+        pool.curve_supply = new_supply;
+        pool.sol_reserve = pool.sol_reserve.checked_sub(sol_out).ok_or(BoomError::Overflow)?;
+        pool.total_volume = pool.total_volume.checked_add(proceeds as u128).ok_or(BoomError::Overflow)?;
+        pool.total_fees = pool.total_fees.checked_add(fee_amount as u128).ok_or(BoomError::Overflow)?;
+        pool.quote_fees = pool.quote_fees.checked_add(fee_amount).ok_or(BoomError::Overflow)?;
+
+        emit!(SwapExecuted {
+            round_id: pool.round_id,
+            user: ctx.accounts.user.key(),
+            is_buy: false,
+            amount_in: token_amount,
+            amount_out: sol_out,
+            fee_amount,
+            fee_bps: pool.fee_bps,
+            new_sol_reserve: pool.sol_reserve,
+            new_token_reserve: pool.token_reserve,
         });
 
         Ok(())
     }
 
-    /// Trigger explosion with Pyth price verification
-    /// Verifies that current market cap >= revealed cap using Pyth oracle
-    pub fn trigger_explosion_with_pyth(
-        ctx: Context<TriggerExplosionWithPyth>,
-        revealed_cap: u64,
+    // ==================== PRESALE EXPLOSION ====================
+
+    /// Initialize explosion tracking for a presale token
+    /// Sets the secret cap hash = sha256(cap_le || salt) with a random 32-byte salt (see cap_commitment).
+    /// Timer is NOT started yet - call start_explosion_timer after LP creation.
+    pub fn init_presale_explosion(
+        ctx: Context<InitPresaleExplosion>,
+        round_id: u64,
+        cap_hash: [u8; 32],
     ) -> Result<()> {
+        let presale = &ctx.accounts.presale_round;
+        require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        presale.require_stage(&[RoundStage::TokenCreated, RoundStage::PoolLive])?;
+
         let explosion = &mut ctx.accounts.presale_explosion;
-        let presale_token = &ctx.accounts.presale_token;
-        let lp_info = &ctx.accounts.lp_info;
-        let price_update = &ctx.accounts.price_update;
+        explosion.round_id = round_id;
+        explosion.cap_hash = cap_hash;
+        explosion.revealed_cap = 0;
+        explosion.explosion_deadline = 0; // Not set until start_explosion_timer
+        explosion.is_exploded = false;
+        explosion.explosion_time = 0;
+        explosion.explosion_reason = ExplosionReason::None;
+        explosion.total_sol_for_payout = 0;
+        explosion.timer_started_at = 0;
+        explosion.cap_range_min = 0;
+        explosion.cap_range_max = 0;
+        explosion.puzzle_seed = [0u8; 32];
+        explosion.puzzle_iterations = 0;
+        explosion.puzzle_ciphertext = 0;
+        explosion.randomness_account = Pubkey::default();
+        explosion.randomness_seed_slot = 0;
+        explosion.roll_randomness = Pubkey::default();
+        explosion.roll_seed_slot = 0;
+        explosion.triggers = ExplosionTriggerConfig::default();
+        explosion.last_random_slot = 0;
+        explosion.heat = HeatModeConfig::default();
+        explosion.total_heat_extension = 0;
+        explosion.jackpot_enabled = false;
+        explosion.puzzle_key_hash = [0u8; 32];
+        explosion.bump = ctx.bumps.presale_explosion;
+        explosion.rent_payer = ctx.accounts.authority.key();
 
-        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        emit!(ExplosionInitialized {
+            round_id,
+            deadline: 0, // Timer not started
+        });
 
-        // Verify the revealed cap matches the committed hash
-        let computed_hash = hash(&revealed_cap.to_le_bytes());
-        require!(computed_hash.to_bytes() == explosion.cap_hash, BoomError::InvalidCapReveal);
+        Ok(())
+    }
 
-        // Get SOL/USD price from Pyth (max 60 seconds old)
-        // SOL/USD feed ID on mainnet: 0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d
-        // For devnet, we'll use a test feed
-        let sol_usd_feed_id: [u8; 32] = [
-            0xef, 0x0d, 0x8b, 0x6f, 0xda, 0x2c, 0xeb, 0xa4,
-            0x1d, 0xa1, 0x5d, 0x40, 0x95, 0xd1, 0xda, 0x39,
-            0x2a, 0x0d, 0x2f, 0x8e, 0xd0, 0xc6, 0xc7, 0xbc,
-            0x0f, 0x4c, 0xfa, 0xc8, 0xc2, 0x80, 0xb5, 0x6d,
-        ];
+    /// Publicly disclose the range [cap_min, cap_max] the authority claims the secret cap lies in
+    /// Must happen before the timer starts so traders know the range upfront.
+    /// Not binding on the cap hash: reveals outside the range are rejected, so a cap outside
+    /// it can never be revealed and the round can only end through the other triggers.
+    pub fn commit_cap_range(
+        ctx: Context<ConfigureExplosion>,
+        cap_min: u64,
+        cap_max: u64,
+    ) -> Result<()> {
+        let protocol = &ctx.accounts.protocol;
+        let explosion = &mut ctx.accounts.presale_explosion;
 
-        let clock = Clock::get()?;
-        let max_age: u64 = 60; // 60 seconds
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.timer_started_at == 0, BoomError::TimerAlreadyStarted);
+        require!(explosion.cap_range_max == 0, BoomError::CapRangeAlreadyCommitted);
+        require!(cap_min <= cap_max && cap_max > 0, BoomError::InvalidCapBounds);
+        protocol.check_cap(cap_min)?;
+        protocol.check_cap(cap_max)?;
 
-        let sol_price = price_update
-            .get_price_no_older_than(&clock, max_age, &sol_usd_feed_id)
-            .map_err(|_| BoomError::PriceStale)?;
+        explosion.cap_range_min = cap_min;
+        explosion.cap_range_max = cap_max;
 
-        // sol_price.price is in units of 10^exponent USD
-        // Typically exponent is -8, so price of 15000000000 means $150
-        let sol_usd_price = sol_price.price as u64; // Price with exponent
-        let exponent = sol_price.exponent; // Usually -8
+        emit!(CapRangeCommitted {
+            round_id: explosion.round_id,
+            cap_min,
+            cap_max,
+        });
 
-        // Calculate token price from LP reserves
-        // token_price_in_sol = sol_reserves / token_reserves
-        // We need LP vault balances for this (passed as accounts)
-        
-        // For now, we verify the cap was revealed correctly
-        // Full market cap calculation would require LP vault account data
-        
-        // Market cap = (token_supply * token_price_in_sol * sol_price_usd)
-        // Since we verified the hash, we trust the revealed_cap
-        // In production, we'd calculate actual market cap from LP state
+        Ok(())
+    }
 
-        explosion.is_exploded = true;
-        explosion.revealed_cap = revealed_cap;
-        explosion.explosion_time = clock.unix_timestamp;
-        explosion.explosion_reason = ExplosionReason::CapHit;
+    /// Publish a time-lock puzzle hiding the secret cap
+    /// key = sha256 applied `iterations` times to `seed`, cap = ciphertext ^ key[..8] (LE).
+    /// The cap hash must use the puzzle key as its salt so the solved key reveals it, and
+    /// key_hash = sha256(key) binds the reveal to this puzzle. The chain can't replay the
+    /// iterations, so solvers check seed/iterations against key_hash off-chain before working.
+    pub fn commit_cap_puzzle(
+        ctx: Context<ConfigureExplosion>,
+        seed: [u8; 32],
+        iterations: u64,
+        ciphertext: u64,
+        key_hash: [u8; 32],
+    ) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
 
-        emit!(PresaleExplosionTriggered {
-            round_id: explosion.round_id,
-            reason: ExplosionReason::CapHit,
-            revealed_cap: Some(revealed_cap),
-        });
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.timer_started_at == 0, BoomError::TimerAlreadyStarted);
+        require!(explosion.puzzle_iterations == 0, BoomError::CapPuzzleAlreadyCommitted);
+        require!(iterations > 0, BoomError::InvalidCapPuzzle);
 
-        emit!(PythPriceUsed {
-            sol_usd_price,
-            exponent,
+        explosion.puzzle_seed = seed;
+        explosion.puzzle_iterations = iterations;
+        explosion.puzzle_ciphertext = ciphertext;
+        explosion.puzzle_key_hash = key_hash;
+
+        emit!(CapPuzzleCommitted {
+            round_id: explosion.round_id,
+            seed,
+            iterations,
+            ciphertext,
+            key_hash,
         });
 
         Ok(())
     }
 
-    /// Trigger explosion due to time limit
-    /// Anyone can call this once the deadline passes
-    pub fn trigger_presale_explosion_time(
-        ctx: Context<TriggerPresaleExplosionTime>,
-    ) -> Result<()> {
+    /// Derive the cap from a randomness provider instead of an authority-chosen hash
+    /// Init the explosion with a zero cap_hash, then bind it to an unrevealed randomness
+    /// account. Nobody knows the cap until the randomness is revealed and checked.
+    pub fn commit_cap_randomness(ctx: Context<CommitCapRandomness>) -> Result<()> {
         let explosion = &mut ctx.accounts.presale_explosion;
-        let clock = Clock::get()?;
 
         require!(!explosion.is_exploded, BoomError::AlreadyExploded);
-        require!(explosion.explosion_deadline > 0, BoomError::NoDeadlineSet);
-        require!(clock.unix_timestamp >= explosion.explosion_deadline, BoomError::DeadlineNotReached);
+        require!(explosion.timer_started_at == 0, BoomError::TimerAlreadyStarted);
+        require!(explosion.cap_hash == [0u8; 32], BoomError::CapAlreadySet);
+        require!(
+            explosion.randomness_account == Pubkey::default(),
+            BoomError::CapAlreadySet
+        );
+        let reading = read_randomness(&ctx.accounts.randomness)?;
+        require!(reading.value.is_none(), BoomError::RandomnessAlreadyRevealed);
 
-        explosion.is_exploded = true;
-        explosion.explosion_time = clock.unix_timestamp;
-        explosion.explosion_reason = ExplosionReason::TimeLimit;
+        explosion.randomness_account = ctx.accounts.randomness.key();
+        explosion.randomness_seed_slot = reading.seed_slot;
 
-        emit!(PresaleExplosionTriggered {
+        emit!(CapRandomnessCommitted {
             round_id: explosion.round_id,
-            reason: ExplosionReason::TimeLimit,
-            revealed_cap: None,
+            randomness_account: explosion.randomness_account,
         });
 
         Ok(())
     }
 
-    /// Unwind LP after explosion - burns LP tokens, extracts SOL
+    /// Configure the extra explosion triggers for the round
+    /// Zero values disable a trigger. Locked once the timer starts.
+    pub fn set_explosion_triggers(
+        ctx: Context<ConfigureExplosion>,
+        triggers: ExplosionTriggerConfig,
+    ) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
+
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.timer_started_at == 0, BoomError::TimerAlreadyStarted);
+        require!(
+            triggers.drawdown_bps <= 10000 && triggers.slot_probability_ppm <= 1_000_000,
+            BoomError::InvalidExplosionTriggers
+        );
+
+        explosion.triggers = triggers;
+
+        emit!(ExplosionTriggersSet {
+            round_id: explosion.round_id,
+            triggers,
+        });
+
+        Ok(())
+    }
+
+    /// Configure heat mode - large buys push the explosion deadline back
+    /// A zero extension disables it. Locked once the timer starts.
+    pub fn set_heat_mode(ctx: Context<ConfigureExplosion>, heat: HeatModeConfig) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
+
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.timer_started_at == 0, BoomError::TimerAlreadyStarted);
+        require!(
+            heat.extension_seconds >= 0 && heat.max_total_extension >= 0,
+            BoomError::InvalidHeatMode
+        );
+
+        explosion.heat = heat;
+
+        emit!(HeatModeSet {
+            round_id: explosion.round_id,
+            heat,
+        });
+
+        Ok(())
+    }
+
+    /// Start the explosion timer - call this AFTER LP is created
+    /// Sets deadline = now + duration_seconds
+    pub fn start_explosion_timer(
+        ctx: Context<StartExplosionTimer>,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        // LP must be registered before starting timer
+        // (validated by account constraint on lp_info)
+        start_timer(
+            &mut ctx.accounts.presale_explosion,
+            &ctx.accounts.presale_round,
+            duration_seconds,
+            Clock::get()?.unix_timestamp,
+        )
+    }
+
+    /// Trigger explosion by revealing the secret cap
+    /// Oracle/authority calls this when market cap threshold is reached
+    pub fn trigger_presale_explosion_cap(
+        ctx: Context<TriggerPresaleExplosion>,
+        revealed_cap: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
+        
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+
+        // Verify the revealed cap matches the committed hash
+        explosion.verify_cap_reveal(revealed_cap, &salt)?;
+        ctx.accounts.protocol.check_cap(revealed_cap)?;
+
+        explosion.is_exploded = true;
+        explosion.revealed_cap = revealed_cap;
+        explosion.explosion_time = Clock::get()?.unix_timestamp;
+        explosion.explosion_reason = ExplosionReason::CapHit;
+        record_presale_explosion(
+            &mut ctx.accounts.presale_round,
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.protocol,
+            explosion.key(),
+            explosion.explosion_time,
+        )?;
+
+        emit!(PresaleExplosionTriggered {
+            round_id: explosion.round_id,
+            reason: ExplosionReason::CapHit,
+            revealed_cap: Some(revealed_cap),
+        });
+
+        Ok(())
+    }
+
+    /// Trigger explosion with the solved time-lock puzzle key
+    /// The decrypted cap must still match the committed cap hash, so a wrong key is rejected.
+    pub fn trigger_explosion_with_puzzle(
+        ctx: Context<TriggerPresaleExplosion>,
+        puzzle_key: [u8; 32],
+    ) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
+
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.puzzle_iterations > 0, BoomError::NoCapPuzzle);
+        require!(
+            hash(&puzzle_key).to_bytes() == explosion.puzzle_key_hash,
+            BoomError::InvalidCapPuzzle
+        );
+
+        let revealed_cap = decrypt_cap_puzzle(explosion.puzzle_ciphertext, &puzzle_key);
+        explosion.verify_cap_reveal(revealed_cap, &puzzle_key)?;
+        ctx.accounts.protocol.check_cap(revealed_cap)?;
+
+        explosion.is_exploded = true;
+        explosion.revealed_cap = revealed_cap;
+        explosion.explosion_time = Clock::get()?.unix_timestamp;
+        explosion.explosion_reason = ExplosionReason::CapHit;
+        record_presale_explosion(
+            &mut ctx.accounts.presale_round,
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.protocol,
+            explosion.key(),
+            explosion.explosion_time,
+        )?;
+
+        emit!(PresaleExplosionTriggered {
+            round_id: explosion.round_id,
+            reason: ExplosionReason::CapHit,
+            revealed_cap: Some(revealed_cap),
+        });
+
+        Ok(())
+    }
+
+    /// Trigger explosion with a randomness-derived cap
+    /// The cap is derived from the revealed randomness inside the protocol bounds
+    /// (or the disclosed cap range) and checked against the pool's time-weighted market cap.
+    pub fn trigger_explosion_with_vrf(ctx: Context<TriggerExplosionWithVrf>) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
+        let protocol = &ctx.accounts.protocol;
+
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+
+        // A provider re-commit after binding would let its authority re-roll the cap
+        let reading = read_randomness(&ctx.accounts.randomness)?;
+        require!(
+            reading.seed_slot == explosion.randomness_seed_slot,
+            BoomError::InvalidRandomnessAccount
+        );
+        let randomness = reading.value.ok_or(BoomError::RandomnessNotRevealed)?;
+        let (min_cap, max_cap) = if explosion.cap_range_max > 0 {
+            (explosion.cap_range_min, explosion.cap_range_max)
+        } else {
+            (protocol.min_cap, protocol.max_cap)
+        };
+        let derived_cap = derive_cap_from_randomness(&randomness, min_cap, max_cap)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let market_cap = pool_market_cap(&ctx.accounts.pool, ctx.accounts.mint.supply, now)?;
+        require!(market_cap >= derived_cap as u128, BoomError::CapNotReached);
+
+        explosion.is_exploded = true;
+        explosion.cap_hash = cap_commitment(derived_cap, &randomness);
+        explosion.revealed_cap = derived_cap;
+        explosion.explosion_time = now;
+        explosion.explosion_reason = ExplosionReason::CapHit;
+        record_presale_explosion(
+            &mut ctx.accounts.presale_round,
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.protocol,
+            explosion.key(),
+            explosion.explosion_time,
+        )?;
+
+        emit!(PresaleExplosionTriggered {
+            round_id: explosion.round_id,
+            reason: ExplosionReason::CapHit,
+            revealed_cap: Some(derived_cap),
+        });
+
+        Ok(())
+    }
+
+    /// Trigger explosion with Pyth price verification
+    /// Verifies that current market cap >= revealed cap using Pyth oracle
+    pub fn trigger_explosion_with_pyth(
+        ctx: Context<TriggerExplosionWithPyth>,
+        revealed_cap: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
+        let presale_token = &ctx.accounts.presale_token;
+        let lp_info = &ctx.accounts.lp_info;
+        let price_update = &ctx.accounts.price_update;
+
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+
+        // Verify the revealed cap matches the committed hash
+        explosion.verify_cap_reveal(revealed_cap, &salt)?;
+        ctx.accounts.protocol.check_cap(revealed_cap)?;
+
+        // Get SOL/USD price from Pyth (max 60 seconds old)
+        // SOL/USD feed ID on mainnet: 0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d
+        // For devnet, we'll use a test feed
+        let sol_usd_feed_id: [u8; 32] = [
+            0xef, 0x0d, 0x8b, 0x6f, 0xda, 0x2c, 0xeb, 0xa4,
+            0x1d, 0xa1, 0x5d, 0x40, 0x95, 0xd1, 0xda, 0x39,
+            0x2a, 0x0d, 0x2f, 0x8e, 0xd0, 0xc6, 0xc7, 0xbc,
+            0x0f, 0x4c, 0xfa, 0xc8, 0xc2, 0x80, 0xb5, 0x6d,
+        ];
+
+        let clock = Clock::get()?;
+        let max_age: u64 = 60; // 60 seconds
+
+        let sol_price = price_update
+            .get_price_no_older_than(&clock, max_age, &sol_usd_feed_id)
+            .map_err(|_| BoomError::PriceStale)?;
+
+        // sol_price.price is in units of 10^exponent USD
+        // Typically exponent is -8, so price of 15000000000 means $150
+        let sol_usd_price = sol_price.price as u64; // Price with exponent
+        let exponent = sol_price.exponent; // Usually -8
+
+        // Calculate token price from LP reserves
+        // token_price_in_sol = sol_reserves / token_reserves
+        // We need LP vault balances for this (passed as accounts)
+        
+        // For now, we verify the cap was revealed correctly
+        // Full market cap calculation would require LP vault account data
+        
+        // Market cap = (token_supply * token_price_in_sol * sol_price_usd)
+        // Since we verified the hash, we trust the revealed_cap
+        // In production, we'd calculate actual market cap from LP state
+
+        explosion.is_exploded = true;
+        explosion.revealed_cap = revealed_cap;
+        explosion.explosion_time = clock.unix_timestamp;
+        explosion.explosion_reason = ExplosionReason::CapHit;
+        record_presale_explosion(
+            &mut ctx.accounts.presale_round,
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.protocol,
+            explosion.key(),
+            explosion.explosion_time,
+        )?;
+
+        emit!(PresaleExplosionTriggered {
+            round_id: explosion.round_id,
+            reason: ExplosionReason::CapHit,
+            revealed_cap: Some(revealed_cap),
+        });
+
+        emit!(PythPriceUsed {
+            sol_usd_price,
+            exponent,
+        });
+
+        Ok(())
+    }
+
+    /// Trigger explosion due to time limit
+    /// Anyone can call this once the deadline passes
+    pub fn trigger_presale_explosion_time(
+        ctx: Context<TriggerPresaleExplosionTime>,
+    ) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
+        let clock = Clock::get()?;
+
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.explosion_deadline > 0, BoomError::NoDeadlineSet);
+        require!(clock.unix_timestamp >= explosion.explosion_deadline, BoomError::DeadlineNotReached);
+
+        explosion.is_exploded = true;
+        explosion.explosion_time = clock.unix_timestamp;
+        explosion.explosion_reason = ExplosionReason::TimeLimit;
+        record_presale_explosion(
+            &mut ctx.accounts.presale_round,
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.protocol,
+            explosion.key(),
+            explosion.explosion_time,
+        )?;
+
+        emit!(PresaleExplosionTriggered {
+            round_id: explosion.round_id,
+            reason: ExplosionReason::TimeLimit,
+            revealed_cap: None,
+        });
+
+        Ok(())
+    }
+
+    /// Trigger explosion once cumulative pool volume reaches the threshold
+    /// Volume counts every trade, so a wallet trading against itself can wash-trade it
+    /// up for the cost of fees - treat it as a soft signal, not a measure of demand.
+    pub fn trigger_explosion_volume(ctx: Context<TriggerExplosionMetric>) -> Result<()> {
+        let threshold = ctx.accounts.presale_explosion.triggers.volume_threshold;
+        require!(threshold > 0, BoomError::TriggerNotConfigured);
+        require!(ctx.accounts.pool.total_volume >= threshold, BoomError::TriggerNotReached);
+
+        explode_presale(ctx.accounts, ExplosionReason::VolumeThreshold)
+    }
+
+    /// Trigger explosion once enough distinct wallets have bought
+    pub fn trigger_explosion_holders(ctx: Context<TriggerExplosionMetric>) -> Result<()> {
+        let threshold = ctx.accounts.presale_explosion.triggers.holder_threshold;
+        require!(threshold > 0, BoomError::TriggerNotConfigured);
+        require!(ctx.accounts.pool.holder_count >= threshold, BoomError::TriggerNotReached);
+
+        explode_presale(ctx.accounts, ExplosionReason::HolderThreshold)
+    }
+
+    /// Trigger explosion once the price falls drawdown_bps below its all-time high
+    /// Both the high and the current price are time-weighted, so the drop has to hold
+    /// for a while instead of being pushed inside a single transaction.
+    pub fn trigger_explosion_drawdown(ctx: Context<TriggerExplosionMetric>) -> Result<()> {
+        let drawdown_bps = ctx.accounts.presale_explosion.triggers.drawdown_bps;
+        require!(drawdown_bps > 0, BoomError::TriggerNotConfigured);
+
+        let pool = &ctx.accounts.pool;
+        let price = pool.time_weighted_price(Clock::get()?.unix_timestamp);
+        let high = pool.ath_price.max(price);
+        require!(high > 0, BoomError::TriggerNotReached);
+        let trigger_price = (high as u128) * (10000 - drawdown_bps as u128) / 10000;
+        require!((price as u128) <= trigger_price, BoomError::TriggerNotReached);
+
+        explode_presale(ctx.accounts, ExplosionReason::Drawdown)
+    }
+
+    /// Commit an unrevealed randomness account for the next explosion roll
+    /// At most one roll per slot. A pending roll can be replaced once it is
+    /// RANDOM_ROLL_EXPIRY_SLOTS old, so an unrevealed request can't block the trigger.
+    pub fn commit_random_roll(ctx: Context<CommitRandomRoll>) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let explosion = &mut ctx.accounts.presale_explosion;
+
+        require!(explosion.triggers.slot_probability_ppm > 0, BoomError::TriggerNotConfigured);
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(slot > explosion.last_random_slot, BoomError::SlotAlreadyRolled);
+        require!(
+            explosion.roll_randomness == Pubkey::default()
+                || slot >= explosion.last_random_slot.saturating_add(RANDOM_ROLL_EXPIRY_SLOTS),
+            BoomError::RandomRollPending
+        );
+
+        let reading = read_randomness(&ctx.accounts.randomness)?;
+        require!(reading.value.is_none(), BoomError::RandomnessAlreadyRevealed);
+
+        explosion.roll_randomness = ctx.accounts.randomness.key();
+        explosion.roll_seed_slot = reading.seed_slot;
+        explosion.last_random_slot = slot;
+
+        emit!(RandomRollCommitted {
+            round_id: explosion.round_id,
+            randomness_account: explosion.roll_randomness,
+            slot,
+        });
+
+        Ok(())
+    }
+
+    /// Resolve the committed roll once its randomness is revealed
+    /// Explodes the round with probability slot_probability_ppm per roll. The outcome is
+    /// fixed at commit time, so nobody can pick a favourable slot to roll in.
+    pub fn trigger_explosion_random_slot(ctx: Context<TriggerExplosionRandomSlot>) -> Result<()> {
+        let probability_ppm = ctx.accounts.presale_explosion.triggers.slot_probability_ppm;
+        require!(probability_ppm > 0, BoomError::TriggerNotConfigured);
+
+        let explosion = &mut ctx.accounts.presale_explosion;
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.roll_randomness != Pubkey::default(), BoomError::NoRandomRoll);
+
+        let reading = read_randomness(&ctx.accounts.randomness)?;
+        require!(
+            reading.seed_slot == explosion.roll_seed_slot,
+            BoomError::InvalidRandomnessAccount
+        );
+        let randomness = reading.value.ok_or(BoomError::RandomnessNotRevealed)?;
+        explosion.roll_randomness = Pubkey::default();
+
+        let roll = hashv(&[&randomness, explosion.key().as_ref()]).to_bytes();
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&roll[..4]);
+        if u32::from_le_bytes(bytes) % 1_000_000 >= probability_ppm {
+            return Ok(());
+        }
+
+        let accounts = ctx.accounts;
+        let explosion_key = accounts.presale_explosion.key();
+        explode_with_reason(
+            &mut accounts.presale_explosion,
+            explosion_key,
+            &mut accounts.presale_round,
+            &mut accounts.launch,
+            &mut accounts.protocol,
+            ExplosionReason::RandomSlot,
+        )
+    }
+
+    /// Unwind LP after explosion - burns LP tokens, extracts SOL
     /// Called by authority after explosion triggers
     /// Unwind LP after explosion - burns LP tokens, extracts SOL
     /// Called by authority after explosion triggers
@@ -1309,6 +2977,9 @@ pub mod boom {
     /// 2. Record SOL extracted (passed in, extracted off-chain from AMM)
     /// 3. Calculate remaining supply = mint supply after burn
     /// 4. Initialize payout pool for holders to claim
+    /// Claims close after the round's claim_window (see set_claim_window).
+    /// SPL-quote rounds ignore total_sol_extracted: the pool's whole quote vault
+    /// moves into the payout quote vault and that amount is paid out.
     pub fn unwind_lp(
         ctx: Context<UnwindLp>,
         total_sol_extracted: u64,
@@ -1319,6 +2990,9 @@ pub mod boom {
         
         require!(explosion.is_exploded, BoomError::NotExploded);
         require!(explosion.total_sol_for_payout == 0, BoomError::LpAlreadyUnwound);
+        ctx.accounts
+            .presale_round
+            .advance_stage(&[RoundStage::Exploded], RoundStage::Settled)?;
 
         // Get tokens in LP vault (these need to be burned)
         let lp_tokens_to_burn = token_vault.amount;
@@ -1352,7 +3026,58 @@ pub mod boom {
         // Get remaining supply from mint (after burn)
         // Need to reload mint to get updated supply
         ctx.accounts.mint.reload()?;
-        let remaining_token_supply = ctx.accounts.mint.supply;
+        let mut remaining_token_supply = ctx.accounts.mint.supply;
+        // Unvested winner tokens share in the payout when the round says so
+        let presale = &ctx.accounts.presale_round;
+        // Winners who never claimed are paid a share for their allocation when they do
+        remaining_token_supply = remaining_token_supply
+            .checked_add(presale.unclaimed_allocation(ctx.accounts.presale_token.tokens_per_winner)?)
+            .ok_or(BoomError::Overflow)?;
+        if presale.vesting.unvested_counts_toward_supply {
+            remaining_token_supply = remaining_token_supply
+                .checked_add(presale.unvested_supply())
+                .ok_or(BoomError::Overflow)?;
+        }
+
+        // SPL rounds: move the pool's quote reserve (and quote fees) to the payout vault
+        let total_sol_extracted = if pool.quote_mint == Pubkey::default() {
+            total_sol_extracted
+        } else {
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.pool_quote_vault,
+                &ctx.accounts.payout_quote_vault,
+                &ctx.accounts.quote_token_program,
+            )?;
+            let amount = quote.vault.amount;
+            if amount > 0 {
+                quote.withdraw(
+                    quote_authority_info(&ctx.accounts.quote_authority)?,
+                    pool.round_id,
+                    pool.quote_authority_bump,
+                    amount,
+                )?;
+            }
+            amount
+        };
+
+        // Carve the last-buyer jackpot out of the pro-rata payout
+        let jackpot_amount = if explosion.jackpot_enabled {
+            let jackpot = ctx.accounts.jackpot.as_mut().ok_or(BoomError::MissingJackpotAccount)?;
+            let amount = jackpot.settle(total_sol_extracted, pool.quote_mint, pool.quote_authority_bump)?;
+            emit!(JackpotFunded {
+                round_id: explosion.round_id,
+                total_amount: amount,
+                amount_per_winner: jackpot.amount_per_winner,
+                winners: jackpot.buyer_count,
+            });
+            amount
+        } else {
+            0
+        };
+        let payout_sol = total_sol_extracted
+            .checked_sub(jackpot_amount)
+            .ok_or(BoomError::Overflow)?;
 
         // Record payout pool info
         explosion.total_sol_for_payout = total_sol_extracted;
@@ -1360,19 +3085,33 @@ pub mod boom {
         // Initialize payout tracking
         let payout_pool = &mut ctx.accounts.payout_pool;
         payout_pool.round_id = explosion.round_id;
-        payout_pool.total_sol = total_sol_extracted;
+        payout_pool.total_sol = payout_sol;
         payout_pool.remaining_supply = remaining_token_supply;
         payout_pool.claimed_count = 0;
         payout_pool.bump = ctx.bumps.payout_pool;
+        payout_pool.rent_payer = ctx.accounts.authority.key();
+        ctx.accounts.launch.payout_pool = payout_pool.key();
+        payout_pool.quote_mint = pool.quote_mint;
+        payout_pool.quote_authority_bump = pool.quote_authority_bump;
+        payout_pool.mint = pool.mint;
+        payout_pool.claimed_sol = 0;
+        payout_pool.claimed_tokens = 0;
+        payout_pool.claim_deadline = ctx
+            .accounts
+            .presale_round
+            .claim_deadline(Clock::get()?.unix_timestamp)?;
+        payout_pool.swept = false;
+        payout_pool.cranked_count = 0;
+        payout_pool.cranked_sol = 0;
 
         emit!(LpUnwound {
             round_id: explosion.round_id,
-            total_sol: total_sol_extracted,
+            total_sol: payout_sol,
             remaining_supply: remaining_token_supply,
         });
 
         msg!("LP unwound: {} SOL for payout, {} tokens remaining in circulation", 
-             total_sol_extracted, remaining_token_supply);
+             payout_sol, remaining_token_supply);
 
         Ok(())
     }
@@ -1388,27 +3127,53 @@ pub mod boom {
 
         require!(explosion.is_exploded, BoomError::NotExploded);
         require!(payout_pool.total_sol > 0, BoomError::LpNotUnwound);
+        require!(
+            !payout_pool.swept && !payout_pool.claim_window_closed(Clock::get()?.unix_timestamp),
+            BoomError::ClaimWindowClosed
+        );
 
         // Get user's token balance
         let user_tokens = user_token_account.amount;
         require!(user_tokens > 0, BoomError::NoTokensToClaim);
 
-        // Calculate proportional payout
-        // payout = (user_tokens / remaining_supply) * total_sol
-        let payout_amount = (user_tokens as u128)
-            .checked_mul(payout_pool.total_sol as u128)
-            .ok_or(BoomError::Overflow)?
-            .checked_div(payout_pool.remaining_supply as u128)
-            .ok_or(BoomError::Overflow)? as u64;
-
+        // Calculate proportional payout against what is still unclaimed
+        let payout_amount = payout_pool.payout_for(user_tokens)?;
         require!(payout_amount > 0, BoomError::PayoutTooSmall);
 
-        // Transfer SOL from payout vault to user
-        let payout_vault = &ctx.accounts.payout_vault;
-        let user = &ctx.accounts.user;
+        if payout_pool.quote_mint == Pubkey::default() {
+            // payout_vault is system-owned, so it can only be debited through the System Program
+            let round_id_bytes = payout_pool.round_id.to_le_bytes();
+            let seeds = &[
+                b"payout_vault".as_ref(),
+                round_id_bytes.as_ref(),
+                &[ctx.bumps.payout_vault],
+            ];
+            let signer_seeds = &[&seeds[..]];
 
-        **payout_vault.to_account_info().try_borrow_mut_lamports()? -= payout_amount;
-        **user.to_account_info().try_borrow_mut_lamports()? += payout_amount;
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payout_vault.to_account_info(),
+                    to: ctx.accounts.user.to_account_info(),
+                },
+                signer_seeds,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, payout_amount)?;
+        } else {
+            // Transfer quote tokens from the payout quote vault to user
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.payout_quote_vault,
+                &ctx.accounts.user_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            quote.withdraw(
+                quote_authority_info(&ctx.accounts.quote_authority)?,
+                payout_pool.round_id,
+                payout_pool.quote_authority_bump,
+                payout_amount,
+            )?;
+        }
 
         // Burn user's tokens
         let burn_accounts = token_2022::Burn {
@@ -1422,7 +3187,7 @@ pub mod boom {
         );
         token_2022::burn(cpi_ctx, user_tokens)?;
 
-        payout_pool.claimed_count += 1;
+        payout_pool.record_claim(payout_amount, user_tokens)?;
 
         emit!(PayoutClaimed {
             round_id: explosion.round_id,
@@ -1433,6 +3198,282 @@ pub mod boom {
         Ok(())
     }
 
+    /// Sweep payouts and jackpot shares nobody claimed before the claim deadline
+    /// Permissionless - the remainder goes to Protocol.treasury or, for SOL rounds,
+    /// to the next round's pool vault where it seeds liquidity. NextRound keeps only
+    /// the sequencer's unclaimed_carry_over_bps share; the rest goes to the treasury.
+    pub fn sweep_unclaimed_payout(
+        ctx: Context<SweepUnclaimedPayout>,
+        destination: SweepDestination,
+    ) -> Result<()> {
+        let payout_pool = &mut ctx.accounts.payout_pool;
+
+        require!(!payout_pool.swept, BoomError::AlreadySwept);
+        require!(
+            payout_pool.claim_window_closed(Clock::get()?.unix_timestamp),
+            BoomError::ClaimWindowOpen
+        );
+
+        // Unclaimed jackpot shares sit in the same vault and expire with the payouts
+        let jackpot_unclaimed = match load_jackpot(&ctx.accounts.jackpot)? {
+            Some(jackpot) => jackpot.unclaimed_amount()?,
+            None => 0,
+        };
+        let unclaimed = payout_pool
+            .total_sol
+            .checked_sub(payout_pool.claimed_sol)
+            .ok_or(BoomError::Overflow)?
+            .checked_add(jackpot_unclaimed)
+            .ok_or(BoomError::Overflow)?;
+        let round_id = payout_pool.round_id;
+
+        let (amount, destination_key) = if payout_pool.quote_mint == Pubkey::default() {
+            let payout_vault_info = ctx.accounts.payout_vault.to_account_info();
+            let amount = unclaimed.min(payout_vault_info.lamports());
+
+            // NextRound seeds only the sequencer's carry-over share; the rest goes to the treasury
+            let carry = match destination {
+                SweepDestination::Treasury => 0,
+                SweepDestination::NextRound => {
+                    let sequencer = ctx
+                        .accounts
+                        .sequencer
+                        .as_mut()
+                        .ok_or(BoomError::MissingSweepDestination)?;
+                    let round_seed = ctx
+                        .accounts
+                        .round_seed
+                        .as_mut()
+                        .ok_or(BoomError::MissingSweepDestination)?;
+                    let carry = ((amount as u128)
+                        .checked_mul(sequencer.unclaimed_carry_over_bps as u128)
+                        .ok_or(BoomError::Overflow)?
+                        / 10000) as u64;
+                    round_seed.record(
+                        round_id + 1,
+                        ctx.bumps.round_seed.ok_or(BoomError::MissingSweepDestination)?,
+                        ctx.accounts.caller.key(),
+                        0,
+                        carry,
+                    )?;
+                    sequencer.record_carry_over(carry)?;
+                    emit!(RoundSeedCarriedOver {
+                        from_round: round_id,
+                        to_round: round_id + 1,
+                        from_fees: 0,
+                        from_unclaimed: carry,
+                        total_seed: round_seed.total()?,
+                    });
+                    carry
+                }
+            };
+            let to_treasury = amount - carry;
+
+            let round_id_bytes = round_id.to_le_bytes();
+            let seeds = &[
+                b"payout_vault".as_ref(),
+                round_id_bytes.as_ref(),
+                &[ctx.bumps.payout_vault],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let mut destination_key = Pubkey::default();
+            for (to, lamports) in [
+                (ctx.accounts.next_sol_vault.as_ref(), carry),
+                (ctx.accounts.treasury.as_ref(), to_treasury),
+            ] {
+                if lamports == 0 {
+                    continue;
+                }
+                let to = to.ok_or(BoomError::MissingSweepDestination)?.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: payout_vault_info.clone(),
+                        to: to.clone(),
+                    },
+                    signer_seeds,
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+                if destination_key == Pubkey::default() {
+                    destination_key = to.key();
+                }
+            }
+            (amount, destination_key)
+        } else {
+            // Quote mints can differ between rounds - SPL remainders only go to the treasury
+            require!(
+                destination == SweepDestination::Treasury,
+                BoomError::UnsupportedQuoteMint
+            );
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.payout_quote_vault,
+                &ctx.accounts.treasury_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            let amount = unclaimed.min(quote.vault.amount);
+            quote.withdraw(
+                quote_authority_info(&ctx.accounts.quote_authority)?,
+                round_id,
+                payout_pool.quote_authority_bump,
+                amount,
+            )?;
+            (amount, quote.counterparty.key())
+        };
+
+        payout_pool.swept = true;
+
+        emit!(UnclaimedPayoutSwept {
+            round_id,
+            amount,
+            destination,
+            destination_account: destination_key,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank: pay out a batch of holders without their signatures
+    /// remaining_accounts = [holder_token_account, recipient] pairs, where the
+    /// recipient is the holder's wallet (SOL rounds) or quote token account.
+    /// Tokens are burned through the mint's PermanentDelegate (payout_delegate PDA).
+    pub fn crank_payouts<'info>(ctx: Context<'_, '_, 'info, 'info, CrankPayouts<'info>>) -> Result<()> {
+        let explosion = &ctx.accounts.presale_explosion;
+        require!(explosion.is_exploded, BoomError::NotExploded);
+        require!(ctx.accounts.payout_pool.total_sol > 0, BoomError::LpNotUnwound);
+        require!(
+            !ctx.accounts.payout_pool.swept
+                && !ctx.accounts.payout_pool.claim_window_closed(Clock::get()?.unix_timestamp),
+            BoomError::ClaimWindowClosed
+        );
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2) && !ctx.remaining_accounts.is_empty(),
+            BoomError::InvalidCrankAccounts
+        );
+
+        // The mint must delegate burns to this round's payout_delegate PDA
+        let payout_delegate = ctx.accounts.payout_delegate.key();
+        require!(
+            mint_permanent_delegate(&ctx.accounts.mint.to_account_info())? == Some(payout_delegate),
+            BoomError::MissingPermanentDelegate
+        );
+
+        let round_id = ctx.accounts.payout_pool.round_id;
+        let round_id_bytes = round_id.to_le_bytes();
+        let delegate_seeds = &[
+            b"payout_delegate".as_ref(),
+            round_id_bytes.as_ref(),
+            &[ctx.bumps.payout_delegate],
+        ];
+        let vault_seeds = &[
+            b"payout_vault".as_ref(),
+            round_id_bytes.as_ref(),
+            &[ctx.bumps.payout_vault],
+        ];
+        let delegate_signer = &[&delegate_seeds[..]];
+        let vault_signer = &[&vault_seeds[..]];
+        let mint_key = ctx.accounts.mint.key();
+        let quote_mint = ctx.accounts.payout_pool.quote_mint;
+
+        let mut processed: u32 = 0;
+        let mut total_paid: u64 = 0;
+
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let holder_info = &pair[0];
+            let recipient_info = &pair[1];
+
+            let holder_account = InterfaceAccount::<TokenAccountInterface>::try_from(holder_info)?;
+            require!(holder_account.mint == mint_key, BoomError::InvalidMint);
+            let user_tokens = holder_account.amount;
+            if user_tokens == 0 {
+                continue;
+            }
+
+            let payout_amount = ctx.accounts.payout_pool.payout_for(user_tokens)?;
+            if payout_amount == 0 {
+                continue;
+            }
+
+            if quote_mint == Pubkey::default() {
+                require!(
+                    recipient_info.key() == holder_account.owner,
+                    BoomError::InvalidCrankAccounts
+                );
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payout_vault.to_account_info(),
+                        to: recipient_info.clone(),
+                    },
+                    vault_signer,
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, payout_amount)?;
+            } else {
+                let recipient = InterfaceAccount::<TokenAccountInterface>::try_from(recipient_info)?;
+                require!(
+                    recipient.owner == holder_account.owner && recipient.mint == quote_mint,
+                    BoomError::InvalidQuoteAccount
+                );
+                let quote = SplQuote {
+                    mint: ctx.accounts.quote_mint.as_deref().ok_or(BoomError::MissingQuoteAccounts)?,
+                    vault: ctx.accounts.payout_quote_vault.as_deref().ok_or(BoomError::MissingQuoteAccounts)?,
+                    counterparty: &recipient,
+                    token_program: ctx.accounts.quote_token_program.as_ref().ok_or(BoomError::MissingQuoteAccounts)?,
+                };
+                quote.withdraw(
+                    quote_authority_info(&ctx.accounts.quote_authority)?,
+                    round_id,
+                    ctx.accounts.payout_pool.quote_authority_bump,
+                    payout_amount,
+                )?;
+            }
+
+            // Burn the holder's tokens as permanent delegate
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: holder_info.clone(),
+                    authority: ctx.accounts.payout_delegate.to_account_info(),
+                },
+                delegate_signer,
+            );
+            token_2022::burn(cpi_ctx, user_tokens)?;
+
+            ctx.accounts.payout_pool.record_claim(payout_amount, user_tokens)?;
+
+            emit!(PayoutClaimed {
+                round_id,
+                user: holder_account.owner,
+                amount: payout_amount,
+            });
+
+            processed += 1;
+            total_paid = total_paid.checked_add(payout_amount).ok_or(BoomError::Overflow)?;
+        }
+
+        let payout_pool = &mut ctx.accounts.payout_pool;
+        payout_pool.cranked_count = payout_pool
+            .cranked_count
+            .checked_add(processed)
+            .ok_or(BoomError::Overflow)?;
+        payout_pool.cranked_sol = payout_pool
+            .cranked_sol
+            .checked_add(total_paid)
+            .ok_or(BoomError::Overflow)?;
+
+        emit!(PayoutsCranked {
+            round_id,
+            processed,
+            total_paid,
+            claimed_count: payout_pool.claimed_count,
+            claimed_sol: payout_pool.claimed_sol,
+        });
+
+        Ok(())
+    }
+
     // ==================== ROUND SEQUENCER INSTRUCTIONS ====================
 
     /// Initialize the round sequencer for automatic round progression
@@ -1452,6 +3493,10 @@ pub mod boom {
         sequencer.default_lottery_spots = default_lottery_spots;
         sequencer.default_min_deposit = default_min_deposit;
         sequencer.default_max_deposit = default_max_deposit;
+        sequencer.fee_carry_over_bps = 0;
+        sequencer.unclaimed_carry_over_bps = 10000;
+        sequencer.total_carried_over = 0;
+        sequencer.default_timer_duration = 0;
         sequencer.bump = ctx.bumps.sequencer;
         Ok(())
     }
@@ -1491,6 +3536,201 @@ pub mod boom {
         Ok(())
     }
 
+    /// Configure how much of a round's fees and unclaimed payouts seed the next round
+    pub fn set_carry_over(
+        ctx: Context<UpdateRoundDefaults>,
+        fee_carry_over_bps: u16,
+        unclaimed_carry_over_bps: u16,
+    ) -> Result<()> {
+        require!(
+            fee_carry_over_bps <= 10000 && unclaimed_carry_over_bps <= 10000,
+            BoomError::InvalidCarryOver
+        );
+
+        let sequencer = &mut ctx.accounts.sequencer;
+        sequencer.fee_carry_over_bps = fee_carry_over_bps;
+        sequencer.unclaimed_carry_over_bps = unclaimed_carry_over_bps;
+
+        emit!(CarryOverSet {
+            fee_carry_over_bps,
+            unclaimed_carry_over_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Set the explosion timer advance_round starts once a round's pool is live
+    /// Zero leaves the timer to start_explosion_timer.
+    pub fn set_round_timer(
+        ctx: Context<UpdateRoundDefaults>,
+        default_timer_duration: i64,
+    ) -> Result<()> {
+        require!(default_timer_duration >= 0, BoomError::InvalidDuration);
+        ctx.accounts.sequencer.default_timer_duration = default_timer_duration;
+
+        emit!(RoundTimerSet {
+            default_timer_duration,
+        });
+
+        Ok(())
+    }
+
+    /// Move the configured share of an exploded round's fees into the next round's pool vault
+    /// Permissionless, once per round, and only before the LP is unwound.
+    pub fn carry_over_fees(ctx: Context<CarryOverFees>) -> Result<()> {
+        let explosion = &ctx.accounts.presale_explosion;
+        let sequencer = &mut ctx.accounts.sequencer;
+        let pool = &mut ctx.accounts.pool;
+
+        require!(explosion.is_exploded, BoomError::NotExploded);
+        require!(explosion.total_sol_for_payout == 0, BoomError::LpAlreadyUnwound);
+        require!(pool.quote_mint == Pubkey::default(), BoomError::UnsupportedQuoteMint);
+        require!(!pool.fees_carried_over, BoomError::FeesAlreadyCarriedOver);
+
+        let sol_vault_info = ctx.accounts.sol_vault.to_account_info();
+        let available = sol_vault_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0))
+            .min(pool.sol_reserve);
+        let amount = ((pool.quote_fees as u128)
+            .checked_mul(sequencer.fee_carry_over_bps as u128)
+            .ok_or(BoomError::Overflow)?
+            / 10000) as u64;
+        let amount = amount.min(available);
+
+        if amount > 0 {
+            let round_id_bytes = pool.round_id.to_le_bytes();
+            let seeds = &[
+                b"sol_vault".as_ref(),
+                round_id_bytes.as_ref(),
+                &[pool.sol_vault_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: sol_vault_info,
+                    to: ctx.accounts.next_sol_vault.to_account_info(),
+                },
+                signer_seeds,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+            pool.sol_reserve = pool.sol_reserve.checked_sub(amount).ok_or(BoomError::Overflow)?;
+        }
+        pool.fees_carried_over = true;
+
+        let next_round = pool.round_id + 1;
+        let round_seed = &mut ctx.accounts.round_seed;
+        round_seed.record(
+            next_round,
+            ctx.bumps.round_seed,
+            ctx.accounts.caller.key(),
+            amount,
+            0,
+        )?;
+        sequencer.record_carry_over(amount)?;
+
+        emit!(RoundSeedCarriedOver {
+            from_round: pool.round_id,
+            to_round: next_round,
+            from_fees: amount,
+            from_unclaimed: 0,
+            total_seed: round_seed.total()?,
+        });
+
+        Ok(())
+    }
+
+    /// Forward carry-over stranded in an SPL-quote round's SOL vault to the round after it
+    /// SPL-quote pools trade out of their quote vault, so lamports carried into the SOL
+    /// vault would never reach a pool. Permissionless once the pool exists.
+    pub fn forward_round_seed(ctx: Context<ForwardRoundSeed>) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        require!(pool.quote_mint != Pubkey::default(), BoomError::UnsupportedQuoteMint);
+
+        let sol_vault_info = ctx.accounts.sol_vault.to_account_info();
+        let amount = sol_vault_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+
+        if amount > 0 {
+            let round_id_bytes = pool.round_id.to_le_bytes();
+            let seeds = &[
+                b"sol_vault".as_ref(),
+                round_id_bytes.as_ref(),
+                &[pool.sol_vault_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: sol_vault_info,
+                    to: ctx.accounts.next_sol_vault.to_account_info(),
+                },
+                signer_seeds,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        }
+
+        // The forwarded lamports keep their fee/unclaimed split where the record covers them
+        let round_seed = &mut ctx.accounts.round_seed;
+        let from_fees = round_seed.from_fees.min(amount);
+        let from_unclaimed = amount - from_fees;
+        round_seed.from_fees = 0;
+        round_seed.from_unclaimed = 0;
+
+        let next_round = pool.round_id + 1;
+        let next_round_seed = &mut ctx.accounts.next_round_seed;
+        next_round_seed.record(
+            next_round,
+            ctx.bumps.next_round_seed,
+            ctx.accounts.caller.key(),
+            from_fees,
+            from_unclaimed,
+        )?;
+
+        emit!(RoundSeedCarriedOver {
+            from_round: pool.round_id,
+            to_round: next_round,
+            from_fees,
+            from_unclaimed,
+            total_seed: next_round_seed.total()?,
+        });
+
+        Ok(())
+    }
+
+    /// Grow a round sequencer created with an older layout to the current one
+    /// Appended fields start zeroed - a zero default timer leaves timers manual.
+    /// Instructions that load the sequencer fail to deserialize it until this has run.
+    pub fn migrate_round_sequencer(ctx: Context<MigrateRoundSequencer>) -> Result<()> {
+        let info = ctx.accounts.sequencer.to_account_info();
+        let old_len = info.data_len();
+        let new_len = 8 + RoundSequencer::INIT_SPACE;
+        require!(old_len < new_len, BoomError::SequencerUpToDate);
+        require!(
+            info.owner == &crate::ID && old_len >= ROUND_SEQUENCER_V1_SPACE,
+            BoomError::InvalidSequencer
+        );
+        require!(
+            info.try_borrow_data()?[8..40] == ctx.accounts.authority.key().to_bytes(),
+            BoomError::InvalidSequencer
+        );
+
+        grow_account(&info, &ctx.accounts.authority, &ctx.accounts.system_program, new_len)?;
+
+        // Appended fields read back as zero; give them the init_round_sequencer defaults
+        let mut sequencer = RoundSequencer::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        if old_len == ROUND_SEQUENCER_V1_SPACE {
+            sequencer.unclaimed_carry_over_bps = 10000;
+        }
+        sequencer.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+
     /// Automatically start the next round after an explosion
     /// Anyone can call this once a round has exploded
     /// Note: For round 1, use start_presale directly since there's no round 0
@@ -1499,18 +3739,24 @@ pub mod boom {
         new_round_id: u64,
     ) -> Result<()> {
         let sequencer = &mut ctx.accounts.sequencer;
-        let previous_explosion = &ctx.accounts.previous_explosion;
         let clock = Clock::get()?;
 
         // Verify auto-advance is enabled
         require!(sequencer.auto_advance_enabled, BoomError::AutoAdvanceDisabled);
 
-        // Verify the previous round exploded (enforced by context constraint)
-        // and this is the correct next round
-        require!(
-            new_round_id == previous_explosion.round_id + 1,
-            BoomError::InvalidRoundSequence
-        );
+        // The previous round must have exploded or failed
+        // (round sequence enforced by the context seeds)
+        let previous_round = ctx.accounts.previous_presale_round.round_id;
+        let previous_explosion = load_presale_explosion(&ctx.accounts.previous_explosion)?;
+        let previous_exploded = previous_explosion
+            .as_ref()
+            .is_some_and(|explosion| explosion.is_exploded);
+        let timer_started = previous_explosion
+            .as_ref()
+            .is_some_and(|explosion| explosion.explosion_deadline > 0);
+        let previous_failed =
+            !timer_started && ctx.accounts.previous_presale_round.is_failed(clock.unix_timestamp);
+        require!(previous_exploded || previous_failed, BoomError::NotExploded);
 
         // Create the new presale round with default parameters
         let presale = &mut ctx.accounts.new_presale_round;
@@ -1525,16 +3771,47 @@ pub mod boom {
         presale.total_depositors = 0;
         presale.is_finalized = false;
         presale.bump = ctx.bumps.new_presale_round;
+        presale.rent_payer = ctx.accounts.payer.key();
+        presale.allocation = AllocationConfig::default();
+        presale.total_capped_deposited = 0;
+        presale.winners_committed = 0;
+        presale.quote_mint = Pubkey::default();
+        presale.quote_authority_bump = 0;
+        presale.whitelist_root = [0u8; 32];
+        presale.withdraw_penalty_bps = 0;
+        presale.penalties_collected = 0;
+        presale.launch_grace_period = DEFAULT_LAUNCH_GRACE_PERIOD;
+        presale.launch_failed = false;
+        presale.soft_cap = 0;
+        presale.hard_cap = 0;
+        presale.anti_snipe = AntiSnipeConfig::default();
+        presale.total_extension = 0;
+        presale.vesting = VestingConfig::default();
+        presale.vesting_allocated = 0;
+        presale.vesting_minted = 0;
+        presale.settled_deposits = 0;
+        presale.stage = RoundStage::Presale;
+        presale.claim_window = DEFAULT_CLAIM_WINDOW_SECONDS;
+        presale.winner_count = 0;
+        presale.allocation_claimed = 0;
 
         // Update sequencer state
-        let previous_round = previous_explosion.round_id;
-        sequencer.last_explosion_round = previous_round;
+        if previous_exploded {
+            sequencer.last_explosion_round = previous_round;
+        }
         sequencer.current_round = new_round_id;
 
+        let carried_over_seed = match &ctx.accounts.round_seed {
+            Some(round_seed) => round_seed.total()?,
+            None => 0,
+        };
+
         emit!(NextRoundStarted {
             previous_round,
             new_round: new_round_id,
             auto_advanced: true,
+            previous_failed,
+            carried_over_seed,
         });
 
         emit!(PresaleStarted {
@@ -1543,296 +3820,3914 @@ pub mod boom {
             lottery_spots: presale.lottery_spots,
             min_deposit: presale.min_deposit,
             max_deposit: presale.max_deposit,
+            allocation_mode: presale.allocation.mode,
+            quote_mint: presale.quote_mint,
         });
 
         Ok(())
     }
-}
 
-// ==================== EXISTING ACCOUNT CONTEXTS ====================
+    /// Drive the sequencer's current round to its next stage - anyone can call
+    /// Presale: closes deposits once end_time passes (rounds without a lottery draw).
+    /// PoolLive: starts the explosion timer with the sequencer's default duration.
+    /// Token and pool creation stay with the authority, explosions with the triggers.
+    pub fn advance_round(ctx: Context<AdvanceRound>) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+        let now = Clock::get()?.unix_timestamp;
+
+        match presale.stage {
+            RoundStage::Presale => {
+                require!(
+                    presale.allocation.mode != AllocationMode::FixedPerWinner
+                        || presale.soft_cap_missed(now),
+                    BoomError::LotteryDrawRequired
+                );
+                finalize_presale(presale, 0, now)
+            }
+            RoundStage::PoolLive => {
+                let duration = ctx.accounts.sequencer.default_timer_duration;
+                require!(duration > 0, BoomError::RoundTimerNotSet);
+                let explosion = ctx
+                    .accounts
+                    .presale_explosion
+                    .as_mut()
+                    .ok_or(BoomError::MissingExplosionAccount)?;
+                start_timer(explosion, presale, duration, now)
+            }
+            _ => err!(BoomError::InvalidRoundStage),
+        }
+    }
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(init, payer = authority, space = 8 + 99, seeds = [b"protocol"], bump)]
-    pub protocol: Account<'info, Protocol>,
-    /// CHECK: Treasury
-    pub treasury: UncheckedAccount<'info>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    // ==================== JACKPOT ====================
 
-#[derive(Accounts)]
-pub struct CreateBoomToken<'info> {
-    #[account(init, payer = creator, space = 8 + 170, seeds = [b"boom_token", mint.key().as_ref()], bump)]
-    pub boom_token: Box<Account<'info, BoomToken>>,
-    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
-    pub protocol: Box<Account<'info, Protocol>>,
-    pub mint: Account<'info, Mint>,
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-}
+    /// Enable the last-buyer jackpot for a round
+    /// share_bps of the unwound SOL is split between the last winner_count buyers.
+    pub fn init_jackpot(ctx: Context<InitJackpot>, round_id: u64, config: JackpotConfig) -> Result<()> {
+        let explosion = &mut ctx.accounts.presale_explosion;
 
-#[derive(Accounts)]
-pub struct SetSecretCap<'info> {
-    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
-    pub boom_token: Account<'info, BoomToken>,
-    pub authority: Signer<'info>,
-}
+        require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+        require!(explosion.timer_started_at == 0, BoomError::TimerAlreadyStarted);
+        require!(
+            config.share_bps > 0
+                && config.share_bps <= 10000
+                && config.winner_count > 0
+                && config.winner_count as usize <= MAX_JACKPOT_BUYERS,
+            BoomError::InvalidJackpotConfig
+        );
 
-#[derive(Accounts)]
-pub struct TriggerExplosion<'info> {
-    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
-    pub boom_token: Account<'info, BoomToken>,
-    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
-    pub protocol: Account<'info, Protocol>,
-    pub trigger_authority: Signer<'info>,
-}
+        explosion.jackpot_enabled = true;
+
+        let jackpot = &mut ctx.accounts.jackpot;
+        jackpot.round_id = round_id;
+        jackpot.config = config;
+        jackpot.buyers = [Pubkey::default(); MAX_JACKPOT_BUYERS];
+        jackpot.next_index = 0;
+        jackpot.buyer_count = 0;
+        jackpot.settled = false;
+        jackpot.total_amount = 0;
+        jackpot.amount_per_winner = 0;
+        jackpot.claimed_mask = 0;
+        jackpot.quote_mint = Pubkey::default();
+        jackpot.quote_authority_bump = 0;
+        jackpot.bump = ctx.bumps.jackpot;
+        jackpot.rent_payer = ctx.accounts.authority.key();
+
+        emit!(JackpotConfigured { round_id, config });
 
-#[derive(Accounts)]
-pub struct SetTimeLimit<'info> {
-    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
-    pub boom_token: Account<'info, BoomToken>,
-    pub authority: Signer<'info>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct TriggerTimeExplosion<'info> {
-    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
-    pub boom_token: Account<'info, BoomToken>,
-    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
-    pub protocol: Account<'info, Protocol>,
-    /// CHECK: Anyone can trigger once deadline passes
-    pub caller: Signer<'info>,
-}
+    /// Claim one jackpot share - `index` is the winner's slot in the ring buffer
+    pub fn claim_jackpot(ctx: Context<ClaimJackpot>, index: u8) -> Result<()> {
+        let jackpot = &mut ctx.accounts.jackpot;
+        let payout_pool = &ctx.accounts.payout_pool;
 
-// ==================== PRESALE ACCOUNT CONTEXTS ====================
+        require!(jackpot.settled, BoomError::LpNotUnwound);
+        // Jackpot shares expire with the payout claims and are swept with them
+        require!(
+            !payout_pool.swept && !payout_pool.claim_window_closed(Clock::get()?.unix_timestamp),
+            BoomError::ClaimWindowClosed
+        );
+        require!(index < jackpot.buyer_count, BoomError::NotJackpotWinner);
+        require!(
+            jackpot.buyers[index as usize] == ctx.accounts.winner.key(),
+            BoomError::NotJackpotWinner
+        );
+        let bit = 1u16 << index;
+        require!(jackpot.claimed_mask & bit == 0, BoomError::JackpotAlreadyClaimed);
+
+        let amount = jackpot.amount_per_winner;
+        jackpot.claimed_mask |= bit;
+
+        if jackpot.quote_mint == Pubkey::default() {
+            // payout_vault is system-owned, so it can only be debited through the System Program
+            let round_id_bytes = jackpot.round_id.to_le_bytes();
+            let seeds = &[
+                b"payout_vault".as_ref(),
+                round_id_bytes.as_ref(),
+                &[ctx.bumps.payout_vault],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payout_vault.to_account_info(),
+                    to: ctx.accounts.winner.to_account_info(),
+                },
+                signer_seeds,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        } else {
+            let quote = SplQuote::resolve(
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.payout_quote_vault,
+                &ctx.accounts.winner_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            quote.withdraw(
+                quote_authority_info(&ctx.accounts.quote_authority)?,
+                jackpot.round_id,
+                jackpot.quote_authority_bump,
+                amount,
+            )?;
+        }
+
+        emit!(JackpotClaimed {
+            round_id: jackpot.round_id,
+            winner: ctx.accounts.winner.key(),
+            index,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // ==================== MOCK RANDOMNESS ====================
+
+    /// Create a commit-reveal randomness account for tests and local clusters
+    /// Only functional when built with the `mock-randomness` feature.
+    pub fn init_mock_randomness(ctx: Context<InitMockRandomness>, commitment: [u8; 32]) -> Result<()> {
+        mock_randomness::init(ctx, commitment)
+    }
+
+    /// Reveal the value behind a mock randomness commitment
+    pub fn reveal_mock_randomness(ctx: Context<RevealMockRandomness>, value: [u8; 32]) -> Result<()> {
+        mock_randomness::reveal(ctx, value)
+    }
+
+    // ==================== ACCOUNT CLOSING ====================
+
+    /// Close a settled deposit - rent goes back to the depositor
+    pub fn close_user_deposit(ctx: Context<CloseUserDeposit>) -> Result<()> {
+        let presale = &mut ctx.accounts.presale_round;
+        let user_deposit = &ctx.accounts.user_deposit;
+
+        require!(presale.deposit_settled(user_deposit)?, BoomError::AccountNotSettled);
+        // Empty deposits can close any time, funded ones only after finalization
+        if user_deposit.amount > 0 {
+            require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+            presale.settled_deposits += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Close a trader's launch-protection state once the round has exploded, failed or closed
+    pub fn close_trader_state(ctx: Context<CloseTraderState>) -> Result<()> {
+        let explosion = load_presale_explosion(&ctx.accounts.presale_explosion)?;
+        let exploded = explosion.as_ref().is_some_and(|explosion| explosion.is_exploded);
+        let presale = load_presale_round(&ctx.accounts.presale_round)?;
+        require!(
+            round_over(exploded, presale.as_ref(), Clock::get()?.unix_timestamp),
+            BoomError::NotExploded
+        );
+        Ok(())
+    }
+
+    /// Close a vesting schedule once it is fully minted or forfeited
+    pub fn close_winner_vesting(ctx: Context<CloseWinnerVesting>) -> Result<()> {
+        let vesting = &ctx.accounts.winner_vesting;
+        let presale = &ctx.accounts.presale_round;
+        let forfeited = ctx.accounts.presale_explosion.is_exploded
+            && !presale.vesting.unvested_counts_toward_supply;
+
+        require!(
+            vesting.claimed_amount >= vesting.total_amount || forfeited,
+            BoomError::AccountNotSettled
+        );
+
+        Ok(())
+    }
+
+    /// Close the payout pool once every payout is claimed or the remainder swept
+    pub fn close_payout_pool(ctx: Context<ClosePayoutPool>) -> Result<()> {
+        let payout_pool = &ctx.accounts.payout_pool;
+
+        require!(
+            payout_pool.swept || payout_pool.claimed_sol >= payout_pool.total_sol,
+            BoomError::AccountNotSettled
+        );
+        // Jackpot shares are paid from the same vaults - close the jackpot first
+        require!(ctx.accounts.jackpot.data_is_empty(), BoomError::AccountNotSettled);
+
+        // Rounding dust and rent left in the payout vault go to the rent payer
+        sweep_vault_lamports(
+            &ctx.accounts.payout_vault,
+            &ctx.accounts.rent_payer,
+            &ctx.accounts.system_program,
+            b"payout_vault",
+            payout_pool.round_id,
+            ctx.bumps.payout_vault,
+        )?;
+
+        // SPL rounds: close the empty payout quote vault as well
+        if let Some(vault) = &ctx.accounts.payout_quote_vault {
+            close_quote_vault(
+                vault,
+                &ctx.accounts.rent_payer,
+                &ctx.accounts.quote_authority,
+                &ctx.accounts.quote_token_program,
+                payout_pool.round_id,
+                payout_pool.quote_authority_bump,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Close the AMM pool and its token vault after the LP has been unwound
+    pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let explosion = &ctx.accounts.presale_explosion;
+
+        require!(explosion.is_exploded, BoomError::NotExploded);
+        require!(explosion.total_sol_for_payout > 0, BoomError::LpNotUnwound);
+        require!(ctx.accounts.token_vault.amount == 0, BoomError::AccountNotSettled);
+
+        let round_id_bytes = pool.round_id.to_le_bytes();
+        let seeds = &[
+            b"pool".as_ref(),
+            round_id_bytes.as_ref(),
+            &[pool.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::CloseAccount {
+                account: ctx.accounts.token_vault.to_account_info(),
+                destination: ctx.accounts.rent_payer.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_2022::close_account(cpi_ctx)?;
+
+        // Whatever the SOL vault still holds goes to the rent payer
+        sweep_vault_lamports(
+            &ctx.accounts.sol_vault,
+            &ctx.accounts.rent_payer,
+            &ctx.accounts.system_program,
+            b"sol_vault",
+            pool.round_id,
+            pool.sol_vault_bump,
+        )?;
+
+        // SPL rounds: close the empty pool quote vault as well
+        if let Some(vault) = &ctx.accounts.pool_quote_vault {
+            close_quote_vault(
+                vault,
+                &ctx.accounts.rent_payer,
+                &ctx.accounts.quote_authority,
+                &ctx.accounts.quote_token_program,
+                pool.round_id,
+                pool.quote_authority_bump,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Close the presale round once every funded deposit has been settled and closed
+    /// Leaves a tombstone at the round id so the id cannot be started again.
+    pub fn close_presale_round(ctx: Context<ClosePresaleRound>) -> Result<()> {
+        let presale = &ctx.accounts.presale_round;
+        let clock = Clock::get()?;
+
+        require!(presale.is_finalized, BoomError::PresaleNotFinalized);
+        require!(
+            presale.settled_deposits >= presale.total_depositors,
+            BoomError::AccountNotSettled
+        );
+
+        // Successful rounds must have exploded so committed SOL has left the round
+        let explosion = load_presale_explosion(&ctx.accounts.presale_explosion)?;
+        let exploded = explosion.as_ref().is_some_and(|explosion| explosion.is_exploded);
+        require!(exploded || presale.is_failed(clock.unix_timestamp), BoomError::AccountNotSettled);
+
+        // Vesting winners still have tokens to mint from this round
+        let forfeited = exploded && !presale.vesting.unvested_counts_toward_supply;
+        require!(presale.unvested_supply() == 0 || forfeited, BoomError::AccountNotSettled);
+
+        // SPL rounds: close the empty presale quote vault as well
+        if let Some(vault) = &ctx.accounts.quote_vault {
+            close_quote_vault(
+                vault,
+                &ctx.accounts.rent_payer,
+                &ctx.accounts.quote_authority,
+                &ctx.accounts.quote_token_program,
+                presale.round_id,
+                presale.quote_authority_bump,
+            )?;
+        }
+
+        create_round_tombstone(
+            &ctx.accounts.presale_round.to_account_info(),
+            &ctx.accounts.round_tombstone,
+            &ctx.accounts.system_program,
+            presale.round_id,
+            ctx.bumps.round_tombstone,
+        )?;
+
+        Ok(())
+    }
+
+    /// Close the presale token record after the round is closed
+    pub fn close_presale_token(ctx: Context<ClosePresaleToken>) -> Result<()> {
+        require!(ctx.accounts.presale_round.data_is_empty(), BoomError::AccountNotSettled);
+        Ok(())
+    }
+
+    /// Close the jackpot once every share is claimed or swept (or the round is closed unfunded)
+    pub fn close_jackpot(ctx: Context<CloseJackpot>) -> Result<()> {
+        let jackpot = &ctx.accounts.jackpot;
+        if jackpot.settled {
+            let swept = ctx
+                .accounts
+                .payout_pool
+                .as_ref()
+                .is_some_and(|payout_pool| payout_pool.swept);
+            require!(jackpot.all_claimed() || swept, BoomError::AccountNotSettled);
+        } else {
+            require!(ctx.accounts.presale_round.data_is_empty(), BoomError::AccountNotSettled);
+        }
+        Ok(())
+    }
+
+    /// Close the LP registration after the round is closed
+    pub fn close_lp_info(ctx: Context<CloseLpInfo>) -> Result<()> {
+        require!(ctx.accounts.presale_round.data_is_empty(), BoomError::AccountNotSettled);
+        Ok(())
+    }
+
+    /// Close the explosion record after the round, pool and payout pool are closed
+    pub fn close_presale_explosion(ctx: Context<ClosePresaleExplosion>) -> Result<()> {
+        require!(
+            ctx.accounts.presale_round.data_is_empty()
+                && ctx.accounts.pool.data_is_empty()
+                && ctx.accounts.payout_pool.data_is_empty(),
+            BoomError::AccountNotSettled
+        );
+        Ok(())
+    }
+
+    /// Close a round's carry-over record once its pool holds the seed
+    /// SPL-quote rounds must forward the seed first (forward_round_seed).
+    pub fn close_round_seed(ctx: Context<CloseRoundSeed>) -> Result<()> {
+        require!(
+            ctx.accounts.pool.quote_mint == Pubkey::default() || ctx.accounts.round_seed.total()? == 0,
+            BoomError::AccountNotSettled
+        );
+        Ok(())
+    }
+
+    /// Close a launch record once it is finished.
+    /// Presale launches close after their round and explosion record; legacy launches once exploded.
+    pub fn close_launch(ctx: Context<CloseLaunch>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        match launch.kind {
+            LaunchKind::Presale => require!(
+                ctx.accounts.presale_round.data_is_empty()
+                    && ctx.accounts.presale_explosion.data_is_empty(),
+                BoomError::AccountNotSettled
+            ),
+            LaunchKind::Legacy => require!(
+                launch.status == LaunchStatus::Exploded,
+                BoomError::AccountNotSettled
+            ),
+        }
+        Ok(())
+    }
+
+    // ==================== ACCOUNT MIGRATION ====================
+
+    /// Grow a round account created with the first-deployment layout to the current one
+    /// Covers PresaleRound, UserDeposit, PresaleToken, PresaleExplosion, LpInfo, PayoutPool
+    /// and Pool. Appended fields start zeroed; rent payers become the round authority and
+    /// a presale round is placed in the stage its other round records show.
+    /// Instructions that load the account fail to deserialize it until this has run.
+    pub fn migrate_round_account(ctx: Context<MigrateRoundAccount>, round_id: u64) -> Result<()> {
+        let info = ctx.accounts.account.to_account_info();
+        let authority = ctx.accounts.authority.key();
+        require!(
+            info.owner == &crate::ID && info.data_len() >= 8,
+            BoomError::InvalidRoundAccount
+        );
+        // The round authority is the presale round's first field in every layout
+        require!(
+            ctx.accounts.presale_round.data_len() >= 40
+                && ctx.accounts.presale_round.try_borrow_data()?[8..40] == authority.to_bytes(),
+            BoomError::InvalidRoundAccount
+        );
+
+        let old_len = info.data_len();
+        let kind = RoundAccountKind::from_discriminator(&info.try_borrow_data()?[..8])?;
+        let (v1_space, new_len) = kind.spaces();
+        require!(old_len < new_len, BoomError::RoundAccountUpToDate);
+        require!(old_len == v1_space, BoomError::InvalidRoundAccount);
+        require!(
+            info.key() == kind.address(round_id, &info.try_borrow_data()?),
+            BoomError::InvalidRoundAccount
+        );
+
+        grow_account(&info, &ctx.accounts.authority, &ctx.accounts.system_program, new_len)?;
+
+        // Appended fields read back as zero; fill in the ones zero would get wrong
+        let mut data = info.try_borrow_mut_data()?;
+        match kind {
+            RoundAccountKind::PresaleRound => {
+                let explosion = ctx.accounts.presale_explosion.try_borrow_data()?;
+                let exploded = explosion.len() > PRESALE_EXPLOSION_V1_EXPLODED_OFFSET
+                    && explosion[PRESALE_EXPLOSION_V1_EXPLODED_OFFSET] != 0;
+                let token_created = !ctx.accounts.presale_token.data_is_empty();
+                let pool_live =
+                    !ctx.accounts.pool.data_is_empty() || !ctx.accounts.lp_info.data_is_empty();
+                let settled = !ctx.accounts.payout_pool.data_is_empty();
+                update_account(&mut data, |presale: &mut PresaleRound| {
+                    presale.rent_payer = authority;
+                    presale.launch_grace_period = DEFAULT_LAUNCH_GRACE_PERIOD;
+                    presale.claim_window = DEFAULT_CLAIM_WINDOW_SECONDS;
+                    presale.stage = pipeline_stage(
+                        presale.is_finalized,
+                        token_created,
+                        pool_live,
+                        exploded,
+                        settled,
+                    );
+                })?;
+            }
+            RoundAccountKind::UserDeposit => {}
+            RoundAccountKind::PresaleToken => {
+                update_account(&mut data, |token: &mut PresaleToken| token.rent_payer = authority)?;
+            }
+            RoundAccountKind::PresaleExplosion => {
+                update_account(&mut data, |explosion: &mut PresaleExplosion| {
+                    explosion.rent_payer = authority
+                })?;
+            }
+            RoundAccountKind::LpInfo => {
+                update_account(&mut data, |lp_info: &mut LpInfo| lp_info.rent_payer = authority)?;
+            }
+            RoundAccountKind::Pool => {
+                update_account(&mut data, |pool: &mut Pool| pool.rent_payer = authority)?;
+            }
+            RoundAccountKind::PayoutPool => {
+                // The round mint sits right after round_id in every PresaleToken layout
+                let token = ctx.accounts.presale_token.try_borrow_data()?;
+                require!(token.len() >= 48, BoomError::InvalidRoundAccount);
+                let mint = Pubkey::try_from(&token[16..48]).map_err(|_| BoomError::InvalidRoundAccount)?;
+                update_account(&mut data, |payout_pool: &mut PayoutPool| {
+                    payout_pool.rent_payer = authority;
+                    payout_pool.mint = mint;
+                })?;
+            }
+        }
+
+        emit!(RoundAccountMigrated {
+            round_id,
+            account: info.key(),
+            old_len: old_len as u32,
+            new_len: new_len as u32,
+        });
+
+        Ok(())
+    }
+}
+
+// ==================== BONDING CURVE MATH ====================
+
+/// Base units per whole token (9 decimals)
+pub const CURVE_TOKEN_UNIT: u128 = 1_000_000_000;
+/// Fixed-point scale used for exponential growth factors
+const CURVE_PRECISION: u128 = 1_000_000_000_000;
+
+/// Spot price (lamports per whole token) at the given curve supply
+pub fn curve_spot_price(curve: &BondingCurveConfig, supply: u64) -> Result<u128> {
+    let base = curve.base_price as u128;
+    match curve.kind {
+        CurveKind::Linear => {
+            // price = base + slope * supply / (unit * unit)
+            let increase = (curve.slope as u128)
+                .checked_mul(supply as u128)
+                .ok_or(BoomError::Overflow)?
+                / (CURVE_TOKEN_UNIT * CURVE_TOKEN_UNIT);
+            Ok(base.checked_add(increase).ok_or(BoomError::Overflow)?)
+        }
+        CurveKind::Exponential => {
+            // price = base * (1 + growth)^(supply / step_size)
+            let steps = supply / curve.step_size;
+            let factor = curve_growth_factor(curve.growth_bps, steps)?;
+            Ok(base.checked_mul(factor).ok_or(BoomError::Overflow)? / CURVE_PRECISION)
+        }
+    }
+}
+
+/// Total lamports paid to mint `supply` base units starting from zero supply
+/// Trade cost is the difference between two cumulative costs.
+pub fn curve_cumulative_cost(curve: &BondingCurveConfig, supply: u64) -> Result<u128> {
+    let base = curve.base_price as u128;
+    let supply = supply as u128;
+    match curve.kind {
+        CurveKind::Linear => {
+            // F(s) = base * s / unit + slope * s^2 / (2 * unit^3)
+            let base_part = base
+                .checked_mul(supply)
+                .ok_or(BoomError::Overflow)?
+                / CURVE_TOKEN_UNIT;
+            let supply_sq = supply
+                .checked_mul(supply)
+                .ok_or(BoomError::Overflow)?
+                / (CURVE_TOKEN_UNIT * CURVE_TOKEN_UNIT);
+            let slope_part = supply_sq
+                .checked_mul(curve.slope as u128)
+                .ok_or(BoomError::Overflow)?
+                / (2 * CURVE_TOKEN_UNIT);
+            Ok(base_part.checked_add(slope_part).ok_or(BoomError::Overflow)?)
+        }
+        CurveKind::Exponential => {
+            // Price is constant within a step and grows by growth_bps per step:
+            // F(s) = base * step / unit * ((1+g)^k - 1) / g + base * (1+g)^k * r / unit
+            let step = curve.step_size as u128;
+            let steps = (supply / step) as u64;
+            let remainder = supply % step;
+            let factor = curve_growth_factor(curve.growth_bps, steps)?;
+
+            let full_steps = base
+                .checked_mul(step)
+                .ok_or(BoomError::Overflow)?
+                / CURVE_TOKEN_UNIT;
+            let full_steps = full_steps
+                .checked_mul(factor - CURVE_PRECISION)
+                .ok_or(BoomError::Overflow)?
+                .checked_mul(10000)
+                .ok_or(BoomError::Overflow)?
+                / (CURVE_PRECISION * curve.growth_bps as u128);
+            let partial = base
+                .checked_mul(factor)
+                .ok_or(BoomError::Overflow)?
+                / CURVE_PRECISION;
+            let partial = partial
+                .checked_mul(remainder)
+                .ok_or(BoomError::Overflow)?
+                / CURVE_TOKEN_UNIT;
+            Ok(full_steps.checked_add(partial).ok_or(BoomError::Overflow)?)
+        }
+    }
+}
+
+/// (1 + growth_bps / 10000)^steps in CURVE_PRECISION fixed point
+fn curve_growth_factor(growth_bps: u16, steps: u64) -> Result<u128> {
+    let mut base = CURVE_PRECISION + CURVE_PRECISION * growth_bps as u128 / 10000;
+    let mut exp = steps;
+    let mut result = CURVE_PRECISION;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.checked_mul(base).ok_or(BoomError::Overflow)? / CURVE_PRECISION;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = base.checked_mul(base).ok_or(BoomError::Overflow)? / CURVE_PRECISION;
+        }
+    }
+    Ok(result)
+}
+
+// ==================== LAUNCH PROTECTION ====================
+
+/// Enforce per-wallet buy limits and trade cooldowns during the launch window
+/// The window opens when the explosion timer starts; trades before the timer
+/// is started are treated as inside the window.
+fn enforce_launch_protection(
+    pool: &Pool,
+    explosion: Option<&PresaleExplosion>,
+    trader_state: Option<&mut TraderState>,
+    is_buy: bool,
+    amount_in: u64,
+) -> Result<()> {
+    let protection = &pool.launch_protection;
+    if protection.duration_seconds == 0 {
+        return Ok(());
+    }
+
+    let clock = Clock::get()?;
+    let timer_started_at = explosion.map_or(0, |explosion| explosion.timer_started_at);
+    let window_open = timer_started_at == 0
+        || clock.unix_timestamp < timer_started_at.saturating_add(protection.duration_seconds);
+    if !window_open {
+        return Ok(());
+    }
+
+    let trader_state = trader_state.ok_or(BoomError::MissingTraderState)?;
+    if protection.min_slots_between_trades > 0 && trader_state.last_trade_slot > 0 {
+        require!(
+            clock.slot
+                >= trader_state
+                    .last_trade_slot
+                    .saturating_add(protection.min_slots_between_trades),
+            BoomError::TradeCooldownActive
+        );
+    }
+
+    if is_buy && protection.max_buy_per_wallet > 0 {
+        let new_total = trader_state
+            .protected_buy_total
+            .checked_add(amount_in)
+            .ok_or(BoomError::Overflow)?;
+        require!(new_total <= protection.max_buy_per_wallet, BoomError::WalletBuyLimitExceeded);
+        trader_state.protected_buy_total = new_total;
+    }
+
+    trader_state.last_trade_slot = clock.slot;
+
+    Ok(())
+}
+
+/// Fill in a trader state created by init_if_needed and borrow it for the trade
+fn prepare_trader_state<'a>(
+    trader_state: &'a mut Option<Box<Account<'_, TraderState>>>,
+    bump: Option<u8>,
+    round_id: u64,
+    user: Pubkey,
+) -> Option<&'a mut TraderState> {
+    let state: &mut TraderState = trader_state.as_deref_mut()?;
+    state.round_id = round_id;
+    state.user = user;
+    state.bump = bump.unwrap_or(state.bump);
+    Some(state)
+}
+
+// ==================== DYNAMIC FEES ====================
+
+/// Effective fee for a trade given the pool's fee schedule
+/// - Inside the final window before explosion_deadline, the buy/sell fee
+///   is raised to the configured final-window fee
+/// - A volatility surcharge is added from the recent price move
+/// - The result is capped at max_fee_bps (or 10% if unset)
+fn effective_fee_bps(pool: &Pool, explosion: Option<&PresaleExplosion>, is_buy: bool) -> Result<u16> {
+    let schedule = &pool.fee_schedule;
+    let mut fee_bps = pool.fee_bps as u64;
+
+    let deadline = explosion.map_or(0, |explosion| explosion.explosion_deadline);
+    if schedule.final_window_seconds > 0 && deadline > 0 {
+        let now = Clock::get()?.unix_timestamp;
+        if now >= deadline.saturating_sub(schedule.final_window_seconds) {
+            let window_fee = if is_buy {
+                schedule.final_window_buy_fee_bps
+            } else {
+                schedule.final_window_sell_fee_bps
+            };
+            fee_bps = fee_bps.max(window_fee as u64);
+        }
+    }
+
+    if schedule.volatility_multiplier_bps > 0 {
+        let now = Clock::get()?.unix_timestamp;
+        let surcharge = (pool.decayed_move_bps(now) as u64)
+            .checked_mul(schedule.volatility_multiplier_bps as u64)
+            .ok_or(BoomError::Overflow)?
+            / 10000;
+        fee_bps = fee_bps.checked_add(surcharge).ok_or(BoomError::Overflow)?;
+    }
+
+    let cap = if schedule.max_fee_bps > 0 { schedule.max_fee_bps } else { 1000 };
+    Ok(fee_bps.min(cap as u64) as u16)
+}
+
+/// Seconds a price has to hold before the time-weighted price fully reflects it
+pub const PRICE_TWAP_WINDOW: i64 = 300;
+
+/// Seconds over which the recorded price move halves
+pub const PRICE_MOVE_HALF_LIFE: i64 = 60;
+
+/// Track the latest pool price and a decaying measure of recent price moves
+/// recent_move_bps halves every PRICE_MOVE_HALF_LIFE seconds unless a new move is
+/// larger - decay follows time, so a burst of dust trades cannot drain it.
+fn record_price_move(pool: &mut Pool) -> Result<()> {
+    if pool.token_reserve == 0 {
+        return Ok(());
+    }
+
+    // Fold the outgoing price into the time-weighted price before replacing it
+    let now = Clock::get()?.unix_timestamp;
+    let decayed_move_bps = pool.decayed_move_bps(now);
+    pool.twap_price = pool.time_weighted_price(now);
+    pool.twap_updated_at = now;
+    pool.ath_price = pool.ath_price.max(pool.twap_price);
+
+    // Lamports per CURVE_TOKEN_UNIT base units (one whole token at 9 decimals)
+    let price = (pool.sol_reserve as u128)
+        .checked_mul(CURVE_TOKEN_UNIT)
+        .ok_or(BoomError::Overflow)?
+        / pool.token_reserve as u128;
+    let price = u64::try_from(price).map_err(|_| BoomError::Overflow)?;
+
+    let move_bps = if pool.last_price > 0 {
+        let diff = price.abs_diff(pool.last_price) as u128;
+        (diff * 10000 / pool.last_price as u128).min(u16::MAX as u128) as u16
+    } else {
+        0
+    };
+
+    pool.recent_move_bps = decayed_move_bps.max(move_bps);
+    pool.last_price = price;
+
+    Ok(())
+}
+
+/// Pool market cap in lamports from the time-weighted price
+/// Prices are quoted per CURVE_TOKEN_UNIT base units, so this holds for any mint decimals.
+fn pool_market_cap(pool: &Pool, supply: u64, now: i64) -> Result<u128> {
+    Ok((pool.time_weighted_price(now) as u128)
+        .checked_mul(supply as u128)
+        .ok_or(BoomError::Overflow)?
+        / CURVE_TOKEN_UNIT)
+}
+
+// ==================== JACKPOT ====================
+
+/// Push a qualifying buyer into the round's jackpot ring buffer
+fn record_jackpot_buyer(
+    explosion: &PresaleExplosion,
+    jackpot: &mut Option<Box<Account<Jackpot>>>,
+    buyer: Pubkey,
+    amount_in: u64,
+) -> Result<()> {
+    if !explosion.jackpot_enabled || explosion.is_exploded {
+        return Ok(());
+    }
+    let jackpot = jackpot.as_mut().ok_or(BoomError::MissingJackpotAccount)?;
+    if amount_in >= jackpot.config.min_buy {
+        jackpot.record_buyer(buyer);
+    }
+    Ok(())
+}
+
+// ==================== HEAT MODE ====================
+
+/// Extend the running explosion timer for a qualifying buy
+fn extend_heat_timer(explosion: &mut PresaleExplosion, amount_in: u64) -> Result<()> {
+    let heat = explosion.heat;
+    if heat.extension_seconds == 0 || amount_in < heat.min_buy || explosion.is_exploded {
+        return Ok(());
+    }
+
+    // Only a running, unexpired timer can be extended
+    let now = Clock::get()?.unix_timestamp;
+    if explosion.explosion_deadline == 0 || now >= explosion.explosion_deadline {
+        return Ok(());
+    }
+
+    let remaining = heat.max_total_extension.saturating_sub(explosion.total_heat_extension);
+    let extension = heat.extension_seconds.min(remaining);
+    if extension <= 0 {
+        return Ok(());
+    }
+
+    explosion.explosion_deadline = explosion
+        .explosion_deadline
+        .checked_add(extension)
+        .ok_or(BoomError::Overflow)?;
+    explosion.total_heat_extension = explosion
+        .total_heat_extension
+        .checked_add(extension)
+        .ok_or(BoomError::Overflow)?;
+
+    emit!(TimerExtended {
+        round_id: explosion.round_id,
+        new_deadline: explosion.explosion_deadline,
+        total_extension: explosion.total_heat_extension,
+    });
+
+    Ok(())
+}
+
+// ==================== EXPLOSION TRIGGERS ====================
+
+/// Count a wallet toward the pool's holder count on its first buy
+/// Holders are distinct buying wallets; plain token transfers are not tracked.
+/// Trader state is only required once the round has a holder trigger.
+fn track_holder(
+    pool: &mut Pool,
+    explosion: Option<&PresaleExplosion>,
+    trader_state: Option<&mut TraderState>,
+) -> Result<()> {
+    let Some(trader_state) = trader_state else {
+        let holder_trigger = explosion.is_some_and(|explosion| explosion.triggers.holder_threshold > 0);
+        require!(!holder_trigger, BoomError::MissingTraderState);
+        return Ok(());
+    };
+    if !trader_state.counted_holder {
+        trader_state.counted_holder = true;
+        pool.holder_count = pool.holder_count.checked_add(1).ok_or(BoomError::Overflow)?;
+    }
+    Ok(())
+}
+
+/// Mark the round exploded for one of the metric triggers
+fn explode_presale(accounts: &mut TriggerExplosionMetric, reason: ExplosionReason) -> Result<()> {
+    let explosion_key = accounts.presale_explosion.key();
+    explode_with_reason(
+        &mut accounts.presale_explosion,
+        explosion_key,
+        &mut accounts.presale_round,
+        &mut accounts.launch,
+        &mut accounts.protocol,
+        reason,
+    )
+}
+
+fn explode_with_reason(
+    explosion: &mut PresaleExplosion,
+    explosion_key: Pubkey,
+    presale_round: &mut PresaleRound,
+    launch: &mut Launch,
+    protocol: &mut Protocol,
+    reason: ExplosionReason,
+) -> Result<()> {
+    require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+
+    explosion.is_exploded = true;
+    explosion.explosion_time = Clock::get()?.unix_timestamp;
+    explosion.explosion_reason = reason;
+    record_presale_explosion(
+        presale_round,
+        launch,
+        protocol,
+        explosion_key,
+        explosion.explosion_time,
+    )?;
+
+    emit!(PresaleExplosionTriggered {
+        round_id: explosion.round_id,
+        reason,
+        revealed_cap: None,
+    });
+
+    Ok(())
+}
+
+// ==================== QUOTE ASSET ====================
+
+/// Accounts needed to move an SPL quote asset in or out of a round vault
+struct SplQuote<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, MintInterface>,
+    vault: &'a InterfaceAccount<'info, TokenAccountInterface>,
+    counterparty: &'a InterfaceAccount<'info, TokenAccountInterface>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> SplQuote<'a, 'info> {
+    /// Unwrap the optional quote accounts - all of them are required for SPL rounds
+    fn resolve(
+        mint: &'a Option<Box<InterfaceAccount<'info, MintInterface>>>,
+        vault: &'a Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+        counterparty: &'a Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            mint: mint.as_deref().ok_or(BoomError::MissingQuoteAccounts)?,
+            vault: vault.as_deref().ok_or(BoomError::MissingQuoteAccounts)?,
+            counterparty: counterparty.as_deref().ok_or(BoomError::MissingQuoteAccounts)?,
+            token_program: token_program.as_ref().ok_or(BoomError::MissingQuoteAccounts)?,
+        })
+    }
+
+    /// Move quote tokens from the user's account into the vault
+    fn deposit(&self, user: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: self.counterparty.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.vault.to_account_info(),
+                authority: user,
+            },
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+
+    /// Move quote tokens out of the vault, signed by the round's quote_authority PDA
+    fn withdraw(&self, authority: AccountInfo<'info>, round_id: u64, bump: u8, amount: u64) -> Result<()> {
+        let round_id_bytes = round_id.to_le_bytes();
+        let seeds = &[
+            b"quote_authority".as_ref(),
+            round_id_bytes.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.counterparty.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+}
+
+/// Token-2022 mint carries the TransferFeeConfig extension
+/// Such mints deliver less than the transferred amount, so deposits would be overstated.
+fn mint_has_transfer_fee(mint: &AccountInfo) -> Result<bool> {
+    use spl_token_2022::extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions};
+
+    if *mint.owner != Token2022::id() {
+        return Ok(false);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(state.get_extension::<TransferFeeConfig>().is_ok())
+}
+
+fn quote_authority_info<'info>(authority: &Option<UncheckedAccount<'info>>) -> Result<AccountInfo<'info>> {
+    authority
+        .as_ref()
+        .map(|a| a.to_account_info())
+        .ok_or_else(|| error!(BoomError::MissingQuoteAccounts))
+}
+
+// ==================== PAYOUT CLAIMS ====================
+
+/// Claim windows are 0 (no deadline) or at least MIN_CLAIM_WINDOW_SECONDS
+fn validate_claim_window(claim_window: i64) -> Result<()> {
+    require!(
+        claim_window == 0 || claim_window >= MIN_CLAIM_WINDOW_SECONDS,
+        BoomError::ClaimWindowTooShort
+    );
+    Ok(())
+}
+
+// ==================== PAYOUT CRANK ====================
+
+/// Read the PermanentDelegate extension of a Token-2022 mint, if any
+fn mint_permanent_delegate(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    use spl_token_2022::extension::{permanent_delegate::PermanentDelegate, BaseStateWithExtensions, StateWithExtensions};
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(state
+        .get_extension::<PermanentDelegate>()
+        .ok()
+        .and_then(|extension| Option::<Pubkey>::from(extension.delegate)))
+}
+
+// ==================== CAP COMMITMENT ====================
+
+/// Salted hash committing a presale round to its secret cap
+/// The salt keeps the cap from being brute-forced once a narrow range is disclosed.
+pub fn cap_commitment(cap: u64, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&cap.to_le_bytes(), salt]).to_bytes()
+}
+
+// ==================== TIME-LOCK PUZZLE ====================
+
+/// Recover the cap from a solved puzzle key
+/// Solving (off-chain): key = seed; repeat `iterations` times { key = sha256(key) }
+fn decrypt_cap_puzzle(ciphertext: u64, key: &[u8; 32]) -> u64 {
+    let mut mask = [0u8; 8];
+    mask.copy_from_slice(&key[..8]);
+    ciphertext ^ u64::from_le_bytes(mask)
+}
+
+// ==================== RANDOMNESS ====================
+
+/// Switchboard on-demand program (mainnet and devnet deployments)
+const SWITCHBOARD_ON_DEMAND_IDS: [Pubkey; 2] = [
+    anchor_lang::solana_program::pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv"),
+    anchor_lang::solana_program::pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2"),
+];
+
+/// Slots after which a pending random roll can be replaced by a new commit
+pub const RANDOM_ROLL_EXPIRY_SLOTS: u64 = 150;
+
+/// Current state of a randomness provider account
+struct RandomnessReading {
+    seed_slot: u64,          // Slot the request was committed in (0 for the mock provider)
+    value: Option<[u8; 32]>, // None while unrevealed
+}
+
+/// Read a randomness provider account, dispatched on its owner program
+/// Switchboard on-demand accounts are always accepted; the program-owned mock provider
+/// only when built with the `mock-randomness` feature.
+fn read_randomness(info: &AccountInfo) -> Result<RandomnessReading> {
+    if SWITCHBOARD_ON_DEMAND_IDS.contains(info.owner) {
+        return read_switchboard_randomness(&info.try_borrow_data()?);
+    }
+    require!(
+        cfg!(feature = "mock-randomness") && info.owner == &crate::ID,
+        BoomError::UnsupportedRandomnessProvider
+    );
+    let data = info.try_borrow_data()?;
+    let randomness = MockRandomness::try_deserialize(&mut &data[..])?;
+    Ok(RandomnessReading {
+        seed_slot: 0,
+        value: randomness.revealed.then_some(randomness.value),
+    })
+}
+
+/// Parse a Switchboard on-demand RandomnessAccountData account
+/// Layout after the discriminator: authority, queue, seed_slothash, seed_slot, oracle,
+/// reveal_slot, value. The value only counts when revealed after the current commit,
+/// so a stale value left over from an earlier request is never read.
+fn read_switchboard_randomness(data: &[u8]) -> Result<RandomnessReading> {
+    require!(
+        data.len() >= 184 && data[..8] == hash(b"account:RandomnessAccountData").to_bytes()[..8],
+        BoomError::InvalidRandomnessAccount
+    );
+    let read_u64 = |offset: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&data[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    };
+    let seed_slot = read_u64(104);
+    let reveal_slot = read_u64(144);
+    require!(seed_slot > 0, BoomError::InvalidRandomnessAccount);
+
+    let mut value = [0u8; 32];
+    value.copy_from_slice(&data[152..184]);
+    Ok(RandomnessReading {
+        seed_slot,
+        value: (reveal_slot > seed_slot).then_some(value),
+    })
+}
+
+/// Mock provider handlers, compiled in only with the `mock-randomness` feature
+/// anchor 0.30 can't cfg-gate instructions, so without it the entrypoints just reject.
+#[cfg(feature = "mock-randomness")]
+mod mock_randomness {
+    use super::*;
+
+    pub fn init(ctx: Context<InitMockRandomness>, commitment: [u8; 32]) -> Result<()> {
+        let randomness = &mut ctx.accounts.randomness;
+        randomness.authority = ctx.accounts.authority.key();
+        randomness.commitment = commitment;
+        randomness.value = [0u8; 32];
+        randomness.revealed = false;
+        randomness.bump = ctx.bumps.randomness;
+        Ok(())
+    }
+
+    pub fn reveal(ctx: Context<RevealMockRandomness>, value: [u8; 32]) -> Result<()> {
+        let randomness = &mut ctx.accounts.randomness;
+        require!(!randomness.revealed, BoomError::RandomnessAlreadyRevealed);
+        require!(hash(&value).to_bytes() == randomness.commitment, BoomError::InvalidRandomnessReveal);
+
+        randomness.value = value;
+        randomness.revealed = true;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "mock-randomness"))]
+mod mock_randomness {
+    use super::*;
+
+    pub fn init(_ctx: Context<InitMockRandomness>, _commitment: [u8; 32]) -> Result<()> {
+        err!(BoomError::UnsupportedRandomnessProvider)
+    }
+
+    pub fn reveal(_ctx: Context<RevealMockRandomness>, _value: [u8; 32]) -> Result<()> {
+        err!(BoomError::UnsupportedRandomnessProvider)
+    }
+}
+
+/// Map randomness uniformly-ish onto [min_cap, max_cap]
+fn derive_cap_from_randomness(randomness: &[u8; 32], min_cap: u64, max_cap: u64) -> Result<u64> {
+    require!(min_cap <= max_cap, BoomError::InvalidCapBounds);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&randomness[..8]);
+    let range = (max_cap - min_cap) as u128 + 1;
+    let offset = u64::from_le_bytes(bytes) as u128 % range;
+    Ok(min_cap + offset as u64)
+}
+
+// ==================== LAUNCHES ====================
+
+/// Mark a presale launch exploded and count it in the protocol stats
+fn record_presale_explosion(
+    presale_round: &mut PresaleRound,
+    launch: &mut Launch,
+    protocol: &mut Protocol,
+    presale_explosion: Pubkey,
+    now: i64,
+) -> Result<()> {
+    presale_round.advance_stage(&[RoundStage::PoolLive], RoundStage::Exploded)?;
+    launch.presale_explosion = presale_explosion;
+    launch.mark_exploded(now);
+    protocol.total_explosions = protocol.total_explosions.checked_add(1).ok_or(BoomError::Overflow)?;
+    Ok(())
+}
+
+// ==================== ACCOUNT CLOSING ====================
+
+/// Close an empty round quote vault, sending its rent to `destination`
+fn close_quote_vault<'info>(
+    vault: &InterfaceAccount<'info, TokenAccountInterface>,
+    destination: &UncheckedAccount<'info>,
+    authority: &Option<UncheckedAccount<'info>>,
+    token_program: &Option<Interface<'info, TokenInterface>>,
+    round_id: u64,
+    bump: u8,
+) -> Result<()> {
+    require!(vault.amount == 0, BoomError::AccountNotSettled);
+    let token_program = token_program.as_ref().ok_or(BoomError::MissingQuoteAccounts)?;
+
+    let round_id_bytes = round_id.to_le_bytes();
+    let seeds = &[
+        b"quote_authority".as_ref(),
+        round_id_bytes.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: vault.to_account_info(),
+            destination: destination.to_account_info(),
+            authority: quote_authority_info(authority)?,
+        },
+        signer_seeds,
+    );
+    token_interface::close_account(cpi_ctx)
+}
+
+/// Move every lamport out of a system-owned round vault PDA
+fn sweep_vault_lamports<'info>(
+    vault: &UncheckedAccount<'info>,
+    destination: &UncheckedAccount<'info>,
+    system_program: &Program<'info, System>,
+    seed: &[u8],
+    round_id: u64,
+    bump: u8,
+) -> Result<()> {
+    let amount = vault.lamports();
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let round_id_bytes = round_id.to_le_bytes();
+    let seeds = &[seed, round_id_bytes.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: vault.to_account_info(),
+            to: destination.to_account_info(),
+        },
+        signer_seeds,
+    );
+    anchor_lang::system_program::transfer(cpi_ctx, amount)
+}
+
+/// Create the tombstone for a closing round, funded from the round account's rent
+fn create_round_tombstone<'info>(
+    presale_round: &AccountInfo<'info>,
+    tombstone: &UncheckedAccount<'info>,
+    system_program: &Program<'info, System>,
+    round_id: u64,
+    bump: u8,
+) -> Result<()> {
+    let space = 8 + RoundTombstone::INIT_SPACE;
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(tombstone.lamports());
+    **presale_round.try_borrow_mut_lamports()? = presale_round
+        .lamports()
+        .checked_sub(rent)
+        .ok_or(BoomError::Overflow)?;
+    **tombstone.try_borrow_mut_lamports()? += rent;
+
+    let round_id_bytes = round_id.to_le_bytes();
+    let seeds = &[
+        b"round_tombstone".as_ref(),
+        round_id_bytes.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Allocate {
+                account_to_allocate: tombstone.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Assign {
+                account_to_assign: tombstone.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )?;
+
+    let mut data = tombstone.try_borrow_mut_data()?;
+    RoundTombstone { round_id, bump }.try_serialize(&mut &mut data[..])
+}
+
+/// Deserialize a presale round passed unchecked (None once the round is closed)
+fn load_presale_round(account: &UncheckedAccount) -> Result<Option<PresaleRound>> {
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    let data = account.try_borrow_data()?;
+    Ok(Some(PresaleRound::try_deserialize(&mut &data[..])?))
+}
+
+/// A round is over once it exploded, failed or was closed (None)
+fn round_over(exploded: bool, presale: Option<&PresaleRound>, now: i64) -> bool {
+    exploded || presale.is_none_or(|presale| presale.is_failed(now))
+}
+
+// ==================== ACCOUNT MIGRATION ====================
+
+/// First-deployment sizes of the round accounts (see migrate_round_account)
+const PRESALE_ROUND_V1_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 4 + 8 + 8 + 8 + 4 + 1 + 1;
+const USER_DEPOSIT_V1_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 1 + 1;
+const PRESALE_TOKEN_V1_SPACE: usize = 8 + 8 + 32 + 8 + 8 + 1;
+const PRESALE_EXPLOSION_V1_SPACE: usize = 8 + 8 + 32 + 8 + 8 + 1 + 8 + 1 + 8 + 1;
+const LP_INFO_V1_SPACE: usize = 8 + 8 + 32 + 32 + 32 + 32 + 8 + 1;
+const PAYOUT_POOL_V1_SPACE: usize = 8 + 8 + 8 + 8 + 4 + 1;
+const POOL_V1_SPACE: usize = 8 + 8 + 32 + 32 + 32 + 8 + 8 + 2 + 16 + 16 + 1 + 1 + 1;
+
+/// Offset of PresaleExplosion.is_exploded, unchanged since the first deployment
+const PRESALE_EXPLOSION_V1_EXPLODED_OFFSET: usize = 8 + 8 + 32 + 8 + 8;
+
+/// Round accounts that gained appended fields after the first deployment
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum RoundAccountKind {
+    PresaleRound,
+    UserDeposit,
+    PresaleToken,
+    PresaleExplosion,
+    LpInfo,
+    PayoutPool,
+    Pool,
+}
+
+impl RoundAccountKind {
+    fn from_discriminator(discriminator: &[u8]) -> Result<Self> {
+        [
+            (PresaleRound::DISCRIMINATOR, Self::PresaleRound),
+            (UserDeposit::DISCRIMINATOR, Self::UserDeposit),
+            (PresaleToken::DISCRIMINATOR, Self::PresaleToken),
+            (PresaleExplosion::DISCRIMINATOR, Self::PresaleExplosion),
+            (LpInfo::DISCRIMINATOR, Self::LpInfo),
+            (PayoutPool::DISCRIMINATOR, Self::PayoutPool),
+            (Pool::DISCRIMINATOR, Self::Pool),
+        ]
+        .into_iter()
+        .find(|(known, _)| known[..] == *discriminator)
+        .map(|(_, kind)| kind)
+        .ok_or_else(|| error!(BoomError::InvalidRoundAccount))
+    }
+
+    /// (first-deployment size, current size)
+    fn spaces(self) -> (usize, usize) {
+        match self {
+            Self::PresaleRound => (PRESALE_ROUND_V1_SPACE, 8 + PresaleRound::INIT_SPACE),
+            Self::UserDeposit => (USER_DEPOSIT_V1_SPACE, 8 + UserDeposit::INIT_SPACE),
+            Self::PresaleToken => (PRESALE_TOKEN_V1_SPACE, 8 + PresaleToken::INIT_SPACE),
+            Self::PresaleExplosion => (PRESALE_EXPLOSION_V1_SPACE, 8 + PresaleExplosion::INIT_SPACE),
+            Self::LpInfo => (LP_INFO_V1_SPACE, 8 + LpInfo::INIT_SPACE),
+            Self::PayoutPool => (PAYOUT_POOL_V1_SPACE, 8 + PayoutPool::INIT_SPACE),
+            Self::Pool => (POOL_V1_SPACE, 8 + Pool::INIT_SPACE),
+        }
+    }
+
+    /// PDA the account lives at - deposits are keyed by the depositor stored after the discriminator
+    fn address(self, round_id: u64, data: &[u8]) -> Pubkey {
+        let (prefix, depositor): (&[u8], &[u8]) = match self {
+            Self::PresaleRound => (b"presale", &[]),
+            Self::UserDeposit => (b"deposit", &data[8..40]),
+            Self::PresaleToken => (b"presale_token", &[]),
+            Self::PresaleExplosion => (b"presale_explosion", &[]),
+            Self::LpInfo => (b"lp_info", &[]),
+            Self::PayoutPool => (b"payout_pool", &[]),
+            Self::Pool => (b"pool", &[]),
+        };
+        let round_id_bytes = round_id.to_le_bytes();
+        let seeds: &[&[u8]] = if depositor.is_empty() {
+            &[prefix, &round_id_bytes]
+        } else {
+            &[prefix, &round_id_bytes, depositor]
+        };
+        Pubkey::find_program_address(seeds, &crate::ID).0
+    }
+}
+
+/// Realloc a program account to `new_len`, topping its rent up from `payer`
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(info.lamports());
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    info.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Deserialize an account from `data`, apply `update` and write it back
+fn update_account<T: AccountSerialize + AccountDeserialize>(
+    data: &mut [u8],
+    update: impl FnOnce(&mut T),
+) -> Result<()> {
+    let mut account = T::try_deserialize(&mut &data[..])?;
+    update(&mut account);
+    account.try_serialize(&mut &mut data[..])
+}
+
+/// Stage a pre-upgrade round has reached, judged by the round records it has
+fn pipeline_stage(
+    is_finalized: bool,
+    token_created: bool,
+    pool_live: bool,
+    exploded: bool,
+    settled: bool,
+) -> RoundStage {
+    if settled {
+        RoundStage::Settled
+    } else if exploded {
+        RoundStage::Exploded
+    } else if pool_live {
+        RoundStage::PoolLive
+    } else if token_created {
+        RoundStage::TokenCreated
+    } else if is_finalized {
+        RoundStage::Lottery
+    } else {
+        RoundStage::Presale
+    }
+}
+
+// ==================== ROUND STAGES ====================
+
+/// Close deposits and move the round into the Lottery stage
+/// A round that missed its soft cap is marked failed and opens full refunds.
+fn finalize_presale(presale: &mut PresaleRound, winners_count: u32, now: i64) -> Result<()> {
+    require!(!presale.is_finalized, BoomError::PresaleFinalized);
+    require!(now >= presale.end_time, BoomError::PresaleNotEnded);
+
+    presale.is_finalized = true;
+    presale.advance_stage(&[RoundStage::Presale], RoundStage::Lottery)?;
+
+    // Soft cap missed - no winners, every depositor gets a full refund
+    if presale.soft_cap_missed(now) {
+        presale.launch_failed = true;
+
+        emit!(SoftCapMissed {
+            round_id: presale.round_id,
+            total_deposited: presale.total_deposited,
+            soft_cap: presale.soft_cap,
+        });
+
+        return Ok(());
+    }
+
+    emit!(PresaleFinalized {
+        round_id: presale.round_id,
+        total_deposited: presale.total_deposited,
+        total_depositors: presale.total_depositors,
+        winners_count,
+    });
+
+    Ok(())
+}
+
+/// Start a live round's explosion timer
+fn start_timer(
+    explosion: &mut PresaleExplosion,
+    presale: &PresaleRound,
+    duration_seconds: i64,
+    now: i64,
+) -> Result<()> {
+    require!(!explosion.is_exploded, BoomError::AlreadyExploded);
+    require!(explosion.explosion_deadline == 0, BoomError::DeadlineAlreadySet);
+    require!(duration_seconds > 0, BoomError::InvalidDuration);
+    // Past the grace period depositors may already be taking failed-launch refunds
+    require!(!presale.is_failed(now), BoomError::LaunchFailed);
+    presale.require_stage(&[RoundStage::PoolLive])?;
+
+    let deadline = now + duration_seconds;
+    explosion.explosion_deadline = deadline;
+    explosion.timer_started_at = now;
+
+    emit!(TimerStarted {
+        round_id: explosion.round_id,
+        duration_seconds,
+        deadline,
+    });
+
+    Ok(())
+}
+
+// ==================== ROUND STATUS ====================
+
+/// Read a round's explosion account, which may not have been created yet
+fn load_presale_explosion(account: &UncheckedAccount) -> Result<Option<PresaleExplosion>> {
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    let data = account.try_borrow_data()?;
+    Ok(Some(PresaleExplosion::try_deserialize(&mut &data[..])?))
+}
+
+/// Deserialize a jackpot passed unchecked (None if the round has none)
+fn load_jackpot(account: &UncheckedAccount) -> Result<Option<Jackpot>> {
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    let data = account.try_borrow_data()?;
+    Ok(Some(Jackpot::try_deserialize(&mut &data[..])?))
+}
+
+/// Write back an explosion loaded with load_presale_explosion
+fn store_presale_explosion(account: &UncheckedAccount, explosion: &PresaleExplosion) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    explosion.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Mark a legacy launch exploded if its record was migrated (no-op otherwise)
+fn mark_legacy_launch_exploded(account: &UncheckedAccount, now: i64) -> Result<()> {
+    if account.data_is_empty() {
+        return Ok(());
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    let mut launch = Launch::try_deserialize(&mut &data[..])?;
+    launch.mark_exploded(now);
+    launch.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+// ==================== WHITELIST ====================
+
+/// Verify a Merkle proof using sorted-pair hashing
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == root
+}
+
+// ==================== EXISTING ACCOUNT CONTEXTS ====================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = authority, space = 8 + 99, seeds = [b"protocol"], bump)]
+    pub protocol: Account<'info, Protocol>,
+    /// CHECK: Treasury
+    pub treasury: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateBoomToken<'info> {
+    #[account(init, payer = creator, space = 8 + 170, seeds = [b"boom_token", mint.key().as_ref()], bump)]
+    pub boom_token: Box<Account<'info, BoomToken>>,
+    #[account(init, payer = creator, space = 8 + Launch::INIT_SPACE, seeds = [b"launch", mint.key().as_ref()], bump)]
+    pub launch: Box<Account<'info, Launch>>,
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetSecretCap<'info> {
+    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
+    pub boom_token: Account<'info, BoomToken>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerExplosion<'info> {
+    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
+    pub boom_token: Account<'info, BoomToken>,
+    /// Launch record - may not exist yet for tokens created before launches
+    /// CHECK: PDA validated by seeds, updated only when initialized
+    #[account(mut, seeds = [b"launch", boom_token.mint.as_ref()], bump)]
+    pub launch: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    pub trigger_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimeLimit<'info> {
+    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
+    pub boom_token: Account<'info, BoomToken>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerTimeExplosion<'info> {
+    #[account(mut, seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
+    pub boom_token: Account<'info, BoomToken>,
+    /// Launch record - may not exist yet for tokens created before launches
+    /// CHECK: PDA validated by seeds, updated only when initialized
+    #[account(mut, seeds = [b"launch", boom_token.mint.as_ref()], bump)]
+    pub launch: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    /// CHECK: Anyone can trigger once deadline passes
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBoomToken<'info> {
+    #[account(seeds = [b"boom_token", boom_token.mint.as_ref()], bump = boom_token.bump)]
+    pub boom_token: Account<'info, BoomToken>,
+    #[account(init, payer = payer, space = 8 + Launch::INIT_SPACE, seeds = [b"launch", boom_token.mint.as_ref()], bump)]
+    pub launch: Account<'info, Launch>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// ==================== PRESALE ACCOUNT CONTEXTS ====================
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct StartPresale<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PresaleRound::INIT_SPACE,
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    /// Tombstone of a closed round with this id - must not exist
+    /// CHECK: PDA validated by seeds, must be empty
+    #[account(
+        seeds = [b"round_tombstone", round_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = round_tombstone.data_is_empty() @ BoomError::RoundIdRetired
+    )]
+    pub round_tombstone: UncheckedAccount<'info>,
+    /// SPL quote asset for the round - omit for native SOL (no transfer-fee mints)
+    pub quote_mint: Option<InterfaceAccount<'info, MintInterface>>,
+    /// Round sequencer - pass to make this the sequencer's current round
+    #[account(
+        mut,
+        seeds = [b"round_sequencer"],
+        bump = sequencer.bump,
+        has_one = authority
+    )]
+    pub sequencer: Option<Account<'info, RoundSequencer>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct InitQuoteVaults<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    #[account(address = presale_round.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Authority over all of the round's quote vaults
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_authority: UncheckedAccount<'info>,
+
+    /// Holds presale deposits
+    #[account(
+        init,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = quote_authority,
+        token::token_program = quote_token_program,
+        seeds = [b"quote_vault", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_quote_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// Holds the AMM pool's quote reserve
+    #[account(
+        init,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = quote_authority,
+        token::token_program = quote_token_program,
+        seeds = [b"pool_quote_vault", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// Holds quote extracted for explosion payouts
+    #[account(
+        init,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = quote_authority,
+        token::token_program = quote_token_program,
+        seeds = [b"payout_quote_vault", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositPresale<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + UserDeposit::INIT_SPACE,
+        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), depositor.key().as_ref()],
+        bump
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = presale_round.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Presale quote vault
+    #[account(
+        mut,
+        seeds = [b"quote_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Depositor's quote token account
+    #[account(
+        mut,
+        constraint = depositor_quote_account.mint == presale_round.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = depositor_quote_account.owner == depositor.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub depositor_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetWhitelistRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EndPresaleAndLottery<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EndPresale<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    /// Anyone can end a round that needs no lottery draw
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarkWinner<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    /// Presale token - provides the launch price for the winner's purchase cost
+    #[account(
+        seeds = [b"presale_token", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump
+    )]
+    pub presale_token: Account<'info, PresaleToken>,
+    #[account(
+        mut,
+        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), user_deposit.depositor.as_ref()],
+        bump = user_deposit.bump
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    
+    #[account(
+        mut,
+        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), depositor.key().as_ref()],
+        bump = user_deposit.bump,
+        has_one = depositor
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = presale_round.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Presale quote vault
+    #[account(
+        mut,
+        seeds = [b"quote_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Depositor's quote token account (receives the refund)
+    #[account(
+        mut,
+        constraint = depositor_quote_account.mint == presale_round.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = depositor_quote_account.owner == depositor.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub depositor_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawPenalty<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPresaleDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    
+    #[account(
+        mut,
+        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), depositor.key().as_ref()],
+        bump = user_deposit.bump,
+        has_one = depositor
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = presale_round.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Presale quote vault
+    #[account(
+        mut,
+        seeds = [b"quote_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Depositor's quote token account (receives the withdrawal)
+    #[account(
+        mut,
+        constraint = depositor_quote_account.mint == presale_round.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = depositor_quote_account.owner == depositor.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub depositor_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct SetRaiseCaps<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAntiSnipe<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetVesting<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetLaunchGracePeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFailedLaunchRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    /// Explosion tracking - may not exist if the launch never progressed
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_explosion: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), depositor.key().as_ref()],
+        bump = user_deposit.bump,
+        has_one = depositor
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = presale_round.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Presale quote vault
+    #[account(
+        mut,
+        seeds = [b"quote_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Depositor's quote token account (receives the refund)
+    #[account(
+        mut,
+        constraint = depositor_quote_account.mint == presale_round.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = depositor_quote_account.owner == depositor.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub depositor_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWinnerTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        seeds = [b"presale_token", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump,
+        constraint = presale_token.mint == mint.key() @ BoomError::InvalidMint
+    )]
+    pub presale_token: Account<'info, PresaleToken>,
+    
+    /// Explosion tracking - needed to verify trading has started
+    #[account(
+        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump = user_deposit.bump,
+        has_one = depositor @ BoomError::NotAWinner
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    /// The token mint
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// PDA mint authority
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"mint_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Winner's token account (must be initialized beforehand or use init_if_needed)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = winner,
+        token::token_program = token_program
+    )]
+    pub winner_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Vesting schedule PDA - required when the round vests winner allocations
+    #[account(
+        init,
+        payer = winner,
+        space = 8 + WinnerVesting::INIT_SPACE,
+        seeds = [b"vesting", presale_round.round_id.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub winner_vesting: Option<Account<'info, WinnerVesting>>,
+
+    /// CHECK: Validated via has_one
+    pub depositor: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = presale_round.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Presale quote vault
+    #[account(
+        mut,
+        seeds = [b"quote_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Winner's quote token account (receives the excess refund)
+    #[account(
+        mut,
+        constraint = winner_quote_account.mint == presale_round.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = winner_quote_account.owner == winner.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub winner_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+
+    // === Late Claim Accounts (after the explosion only) ===
+    /// Payout pool - late winners take a payout share for their allocation
+    #[account(
+        mut,
+        seeds = [b"payout_pool", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.bump
+    )]
+    pub payout_pool: Option<Box<Account<'info, PayoutPool>>>,
+
+    /// Vault holding SOL for payouts
+    /// CHECK: PDA that holds the extracted SOL
+    #[account(
+        mut,
+        seeds = [b"payout_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_vault: Option<UncheckedAccount<'info>>,
+
+    /// Payout quote vault (SPL quote rounds)
+    #[account(
+        mut,
+        seeds = [b"payout_quote_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        seeds = [b"presale_token", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump,
+        constraint = presale_token.mint == mint.key() @ BoomError::InvalidMint
+    )]
+    pub presale_token: Account<'info, PresaleToken>,
+
+    /// Explosion tracking - unvested tokens may be forfeited at explosion
+    #[account(
+        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", presale_round.round_id.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump = winner_vesting.bump,
+        constraint = winner_vesting.winner == winner.key() @ BoomError::NotAWinner
+    )]
+    pub winner_vesting: Account<'info, WinnerVesting>,
+
+    /// The token mint
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// PDA mint authority
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"mint_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = winner,
+        token::token_program = token_program
+    )]
+    pub winner_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    pub winner: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct CreatePresaleToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PresaleToken::INIT_SPACE,
+        seeds = [b"presale_token", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_token: Account<'info, PresaleToken>,
+
+    /// CHECK: Mint account for Token2022, initialized via CPI
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// CHECK: PDA that will be the mint authority - program controls this
+    #[account(
+        seeds = [b"mint_authority", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Permanent delegate PDA - pass to enable crank_payouts for this mint
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"payout_delegate", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_delegate: Option<UncheckedAccount<'info>>,
+
+    /// Unified launch record for the mint
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Launch::INIT_SPACE,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump
+    )]
+    pub launch: Box<Account<'info, Launch>>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct RegisterPresaleToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PresaleToken::INIT_SPACE,
+        seeds = [b"presale_token", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_token: Account<'info, PresaleToken>,
+
+    /// The externally-created Token2022 mint (with transfer hook)
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// Unified launch record for the mint
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Launch::INIT_SPACE,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump
+    )]
+    pub launch: Box<Account<'info, Launch>>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// NOTE: CreateLiquidityPool context removed - using hybrid approach with Raydium SDK
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct RegisterLp<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LpInfo::INIT_SPACE,
+        seeds = [b"lp_info", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lp_info: Account<'info, LpInfo>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ==================== CUSTOM AMM CONTEXTS ====================
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct CreatePool<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    #[account(
+        seeds = [b"presale_token", round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump,
+        constraint = presale_token.mint == mint.key() @ BoomError::InvalidMint
+    )]
+    pub presale_token: Box<Account<'info, PresaleToken>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The token mint
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Unified launch record for the mint
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch.bump,
+        constraint = launch.round_id == round_id @ BoomError::InvalidLaunch
+    )]
+    pub launch: Box<Account<'info, Launch>>,
+
+    /// Token vault PDA - holds tokens for the pool
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds = [b"token_vault", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// SOL vault PDA - holds SOL for the pool
+    /// CHECK: PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = presale_round.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Presale quote vault (source of the committed quote)
+    #[account(
+        mut,
+        seeds = [b"quote_vault", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", round_id.to_le_bytes().as_ref()],
+        bump = presale_round.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    /// Pool quote vault (receives the committed quote)
+    #[account(
+        mut,
+        seeds = [b"pool_quote_vault", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Explosion tracking - launch protection window and fee schedule depend on the timer;
+    /// heat mode buys extend the deadline. May be uninitialized before init_presale_explosion.
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_explosion: UncheckedAccount<'info>,
+
+    /// Last-buyer jackpot ring buffer (required when the round has a jackpot)
+    #[account(
+        mut,
+        seeds = [b"jackpot", pool.round_id.to_le_bytes().as_ref()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Box<Account<'info, Jackpot>>>,
+
+    /// Per-wallet trading state - required while launch protection or the holder trigger is active
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TraderState::INIT_SPACE,
+        seeds = [b"trader", pool.round_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub trader_state: Option<Box<Account<'info, TraderState>>>,
+
+    /// The token mint
+    #[account(
+        constraint = mint.key() == pool.mint @ BoomError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// Pool's token vault
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = pool,
+        constraint = token_vault.key() == pool.token_vault @ BoomError::InvalidVault
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Pool's SOL vault
+    /// CHECK: PDA holding SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    /// User's token account
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = pool.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Pool quote vault
+    #[account(
+        mut,
+        seeds = [b"pool_quote_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// User's quote token account
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == pool.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = user_quote_account.owner == user.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+
+    // === Transfer Hook Accounts ===
+    /// The transfer hook program
+    /// CHECK: Hook program ID
+    pub hook_program: UncheckedAccount<'info>,
+    
+    /// Extra account metas PDA for the mint
+    /// CHECK: PDA derived from ["extra-account-metas", mint]
+    pub extra_account_metas: UncheckedAccount<'info>,
+    
+    /// Hook config PDA
+    /// CHECK: PDA derived from ["hook_config"] in hook program
+    pub hook_config: UncheckedAccount<'info>,
+    
+    /// Hook whitelist PDA for this mint
+    /// CHECK: PDA derived from ["whitelist", mint] in hook program
+    pub hook_whitelist: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetSwapQuote<'info> {
+    #[account(
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Explosion tracking - fee schedule depends on the deadline
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        seeds = [b"presale_explosion", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_explosion: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SyncPoolReserves<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token vault PDA
+    #[account(
+        token::mint = pool.mint,
+        seeds = [b"token_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.token_vault_bump
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// SOL vault PDA
+    /// CHECK: PDA holding SOL
+    #[account(
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    /// Pool quote vault (SPL quote pools only)
+    #[account(
+        seeds = [b"pool_quote_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Option<InterfaceAccount<'info, TokenAccountInterface>>,
+}
+
+/// Accounts for atomic sell (user transfers tokens first, then calls this)
+#[derive(Accounts)]
+pub struct SwapAtomicSell<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Explosion tracking - fee schedule depends on the deadline
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        seeds = [b"presale_explosion", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_explosion: UncheckedAccount<'info>,
+
+    /// Pool's token vault - verify balance increased
+    #[account(
+        token::mint = pool.mint,
+        constraint = token_vault.key() == pool.token_vault @ BoomError::InvalidVault
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Pool's SOL vault - SOL sent from here
+    /// CHECK: PDA holding SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    /// User receiving SOL
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = pool.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Pool quote vault
+    #[account(
+        mut,
+        seeds = [b"pool_quote_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// User's quote token account
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == pool.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = user_quote_account.owner == user.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for atomic buy (SOL in, tokens out via CPI with remaining_accounts)
+#[derive(Accounts)]
+pub struct SwapAtomicBuy<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Explosion tracking - launch protection window and fee schedule depend on the timer;
+    /// heat mode buys extend the deadline. May be uninitialized before init_presale_explosion.
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_explosion: UncheckedAccount<'info>,
+
+    /// Last-buyer jackpot ring buffer (required when the round has a jackpot)
+    #[account(
+        mut,
+        seeds = [b"jackpot", pool.round_id.to_le_bytes().as_ref()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Box<Account<'info, Jackpot>>>,
+
+    /// Per-wallet trading state - required while launch protection or the holder trigger is active
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TraderState::INIT_SPACE,
+        seeds = [b"trader", pool.round_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub trader_state: Option<Box<Account<'info, TraderState>>>,
+
+    /// The token mint
+    #[account(
+        constraint = mint.key() == pool.mint @ BoomError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// Pool's token vault
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = pool,
+        constraint = token_vault.key() == pool.token_vault @ BoomError::InvalidVault
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Pool's SOL vault
+    /// CHECK: PDA holding SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    /// User's token account (receives tokens)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// User sending SOL
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = pool.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Pool quote vault
+    #[account(
+        mut,
+        seeds = [b"pool_quote_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// User's quote token account
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == pool.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = user_quote_account.owner == user.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+
+    // === Transfer Hook Accounts (passed via remaining_accounts in CPI) ===
+    /// The transfer hook program
+    /// CHECK: Hook program ID
+    pub hook_program: UncheckedAccount<'info>,
+    
+    /// Extra account metas PDA for the mint
+    /// CHECK: PDA derived from ["extra-account-metas", mint]
+    pub extra_account_metas: UncheckedAccount<'info>,
+    
+    /// Hook config PDA
+    /// CHECK: PDA derived from ["hook_config"] in hook program
+    pub hook_config: UncheckedAccount<'info>,
+    
+    /// Hook whitelist PDA for this mint
+    /// CHECK: PDA derived from ["whitelist", mint] in hook program
+    pub hook_whitelist: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct DepositPoolTokens<'info> {
+    #[account(
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        seeds = [b"presale_token", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump,
+        constraint = presale_token.mint == mint.key() @ BoomError::InvalidMint
+    )]
+    pub presale_token: Account<'info, PresaleToken>,
+
+    /// The token mint
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// Token vault PDA
+    #[account(
+        mut,
+        token::mint = mint,
+        seeds = [b"token_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Authority's token account
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+// ==================== BONDING CURVE CONTEXTS ====================
+
+#[derive(Accounts)]
+pub struct BondingCurveBuy<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Explosion tracking - the curve stops minting once the round explodes
+    #[account(
+        seeds = [b"presale_explosion", pool.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Box<Account<'info, PresaleExplosion>>,
+
+    /// Last-buyer jackpot ring buffer (required when the round has a jackpot)
+    #[account(
+        mut,
+        seeds = [b"jackpot", pool.round_id.to_le_bytes().as_ref()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Box<Account<'info, Jackpot>>>,
+
+    /// The token mint - minted into on buy and on graduation
+    #[account(
+        mut,
+        constraint = mint.key() == pool.mint @ BoomError::InvalidMint
+    )]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// PDA mint authority
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"mint_authority", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Pool's token vault - receives the LP side at graduation
+    #[account(
+        mut,
+        constraint = token_vault.key() == pool.token_vault @ BoomError::InvalidVault
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// Pool's SOL vault
+    /// CHECK: PDA holding SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    /// User's token account (receives minted tokens)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BondingCurveSell<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The token mint - burned from on sell
+    #[account(
+        mut,
+        constraint = mint.key() == pool.mint @ BoomError::InvalidMint
+    )]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Pool's SOL vault
+    /// CHECK: PDA holding SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    /// User's token account (tokens burned from here)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+// ==================== PRESALE EXPLOSION CONTEXTS ====================
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct InitPresaleExplosion<'info> {
+    #[account(
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PresaleExplosion::INIT_SPACE,
+        seeds = [b"presale_explosion", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureExplosion<'info> {
+    #[account(
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    #[account(seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitCapRandomness<'info> {
+    #[account(
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// CHECK: Randomness provider account, validated by read_randomness
+    pub randomness: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartExplosionTimer<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// Presale round - the timer cannot start once the launch grace period is over
+    #[account(
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    /// LP must be registered before starting timer
+    #[account(
+        seeds = [b"lp_info", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = lp_info.bump
+    )]
+    pub lp_info: Account<'info, LpInfo>,
+
+    /// Authority that can start the timer
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerPresaleExplosion<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// Presale round - moves to the Exploded stage
+    #[account(
+        mut,
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    /// Unified launch record for the round
+    #[account(
+        mut,
+        seeds = [b"launch", launch.mint.as_ref()],
+        bump = launch.bump,
+        constraint = launch.round_id == presale_explosion.round_id @ BoomError::InvalidLaunch,
+        constraint = launch.kind == LaunchKind::Presale @ BoomError::InvalidLaunch
+    )]
+    pub launch: Account<'info, Launch>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+
+    /// Authority or oracle that can trigger explosion
+    pub trigger_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerExplosionWithVrf<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// Presale round - moves to the Exploded stage
+    #[account(
+        mut,
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    /// Unified launch record for the round
+    #[account(
+        mut,
+        seeds = [b"launch", launch.mint.as_ref()],
+        bump = launch.bump,
+        constraint = launch.round_id == presale_explosion.round_id @ BoomError::InvalidLaunch,
+        constraint = launch.kind == LaunchKind::Presale @ BoomError::InvalidLaunch
+    )]
+    pub launch: Account<'info, Launch>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        seeds = [b"pool", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pool.mint @ BoomError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// CHECK: Must be the randomness account committed for this round
+    #[account(address = presale_explosion.randomness_account @ BoomError::InvalidRandomnessAccount)]
+    pub randomness: UncheckedAccount<'info>,
+
+    /// Anyone can trigger once the market cap reaches the derived cap
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerExplosionWithPyth<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// Presale round - moves to the Exploded stage
+    #[account(
+        mut,
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    /// Unified launch record for the round
+    #[account(
+        mut,
+        seeds = [b"launch", launch.mint.as_ref()],
+        bump = launch.bump,
+        constraint = launch.round_id == presale_explosion.round_id @ BoomError::InvalidLaunch,
+        constraint = launch.kind == LaunchKind::Presale @ BoomError::InvalidLaunch
+    )]
+    pub launch: Account<'info, Launch>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        seeds = [b"presale_token", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump
+    )]
+    pub presale_token: Account<'info, PresaleToken>,
+
+    #[account(
+        seeds = [b"lp_info", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = lp_info.bump
+    )]
+    pub lp_info: Account<'info, LpInfo>,
+
+    /// Pyth price update account
+    pub price_update: Account<'info, PriceUpdateV2>,
+
+    /// Anyone can trigger if price threshold met
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerExplosionMetric<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// Presale round - moves to the Exploded stage
+    #[account(
+        mut,
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    /// Unified launch record for the round
+    #[account(
+        mut,
+        seeds = [b"launch", launch.mint.as_ref()],
+        bump = launch.bump,
+        constraint = launch.round_id == presale_explosion.round_id @ BoomError::InvalidLaunch,
+        constraint = launch.kind == LaunchKind::Presale @ BoomError::InvalidLaunch
+    )]
+    pub launch: Account<'info, Launch>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        seeds = [b"pool", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Anyone can trigger once the metric crosses its threshold
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerExplosionRandomSlot<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// Presale round - moves to the Exploded stage
+    #[account(
+        mut,
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    /// Unified launch record for the round
+    #[account(
+        mut,
+        seeds = [b"launch", launch.mint.as_ref()],
+        bump = launch.bump,
+        constraint = launch.round_id == presale_explosion.round_id @ BoomError::InvalidLaunch,
+        constraint = launch.kind == LaunchKind::Presale @ BoomError::InvalidLaunch
+    )]
+    pub launch: Account<'info, Launch>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+
+    /// CHECK: Must be the randomness account committed for the pending roll
+    #[account(address = presale_explosion.roll_randomness @ BoomError::InvalidRandomnessAccount)]
+    pub randomness: UncheckedAccount<'info>,
+
+    /// Anyone can resolve a revealed roll
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitRandomRoll<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// CHECK: Randomness provider account, validated by read_randomness
+    pub randomness: UncheckedAccount<'info>,
+
+    /// Anyone can commit a roll once per slot
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerPresaleExplosionTime<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    /// Presale round - moves to the Exploded stage
+    #[account(
+        mut,
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
+    )]
+    pub presale_round: Box<Account<'info, PresaleRound>>,
+
+    /// Unified launch record for the round
+    #[account(
+        mut,
+        seeds = [b"launch", launch.mint.as_ref()],
+        bump = launch.bump,
+        constraint = launch.round_id == presale_explosion.round_id @ BoomError::InvalidLaunch,
+        constraint = launch.kind == LaunchKind::Presale @ BoomError::InvalidLaunch
+    )]
+    pub launch: Account<'info, Launch>,
+
+    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+
+    /// Anyone can trigger time-based explosion
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(total_sol_extracted: u64, remaining_token_supply: u64)]
+pub struct UnwindLp<'info> {
+    #[account(
+        mut,
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump,
+        has_one = authority
+    )]
+    pub presale_round: Account<'info, PresaleRound>,
+
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PayoutPool::INIT_SPACE,
+        seeds = [b"payout_pool", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_pool: Account<'info, PayoutPool>,
+
+    /// The AMM pool (needed to get token vault info and as burn authority)
+    #[account(
+        seeds = [b"pool", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Presale token - sizes the winner allocation still unclaimed
+    #[account(
+        seeds = [b"presale_token", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump
+    )]
+    pub presale_token: Box<Account<'info, PresaleToken>>,
+
+    /// Unified launch record for the round
+    #[account(
+        mut,
+        seeds = [b"launch", pool.mint.as_ref()],
+        bump = launch.bump
+    )]
+    pub launch: Account<'info, Launch>,
+
+    /// Last-buyer jackpot (required when the round has a jackpot)
+    #[account(
+        mut,
+        seeds = [b"jackpot", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Box<Account<'info, Jackpot>>>,
+
+    /// Pool's token vault - tokens here will be burned
+    #[account(
+        mut,
+        address = pool.token_vault
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Token mint - to burn LP tokens and get remaining supply
+    #[account(
+        mut,
+        address = pool.mint
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = pool.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Pool quote vault - emptied into the payout quote vault
+    #[account(
+        mut,
+        seeds = [b"pool_quote_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Payout quote vault - holders are paid from here
+    #[account(
+        mut,
+        seeds = [b"payout_quote_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimExplosionPayout<'info> {
+    #[account(
+        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    #[account(
+        mut,
+        seeds = [b"payout_pool", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.bump
+    )]
+    pub payout_pool: Account<'info, PayoutPool>,
+
+    /// Vault holding SOL for payouts
+    /// CHECK: PDA that holds the extracted SOL
+    #[account(
+        mut,
+        seeds = [b"payout_vault", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_vault: UncheckedAccount<'info>,
+
+    /// User's token account
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// The round's token mint
+    #[account(
+        mut,
+        address = payout_pool.mint @ BoomError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = payout_pool.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Payout quote vault
+    #[account(
+        mut,
+        seeds = [b"payout_quote_vault", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// User's quote token account (receives the payout)
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == payout_pool.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = user_quote_account.owner == user.key() @ BoomError::InvalidQuoteAccount
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankPayouts<'info> {
+    #[account(
+        seeds = [b"presale_explosion", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
+    #[account(
+        mut,
+        seeds = [b"payout_pool", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.bump
+    )]
+    pub payout_pool: Account<'info, PayoutPool>,
+
+    /// Vault holding SOL for payouts
+    /// CHECK: PDA that holds the extracted SOL
+    #[account(
+        mut,
+        seeds = [b"payout_vault", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_vault: UncheckedAccount<'info>,
+
+    /// The round's token mint
+    #[account(
+        mut,
+        address = payout_pool.mint @ BoomError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    /// Permanent delegate of the mint - burns holder tokens
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"payout_delegate", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_delegate: UncheckedAccount<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = payout_pool.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Payout quote vault
+    #[account(
+        mut,
+        seeds = [b"payout_quote_vault", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedPayout<'info> {
+    #[account(
+        mut,
+        seeds = [b"payout_pool", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.bump
+    )]
+    pub payout_pool: Account<'info, PayoutPool>,
+
+    /// Jackpot - may not exist; its unclaimed shares are swept with the payouts
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        seeds = [b"jackpot", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub jackpot: UncheckedAccount<'info>,
+
+    /// Vault holding SOL for payouts
+    /// CHECK: PDA that holds the extracted SOL
+    #[account(
+        mut,
+        seeds = [b"payout_vault", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    /// Protocol treasury (SweepDestination::Treasury, SOL rounds)
+    /// CHECK: Validated against protocol.treasury
+    #[account(
+        mut,
+        address = protocol.treasury @ BoomError::InvalidSweepDestination
+    )]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Next round's pool SOL vault (SweepDestination::NextRound)
+    /// CHECK: PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [b"sol_vault", (payout_pool.round_id + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_sol_vault: Option<UncheckedAccount<'info>>,
+
+    /// Round sequencer - required for SweepDestination::NextRound (carry-over share)
+    #[account(
+        mut,
+        seeds = [b"round_sequencer"],
+        bump = sequencer.bump
+    )]
+    pub sequencer: Option<Account<'info, RoundSequencer>>,
+
+    /// Next round's carry-over record - required for SweepDestination::NextRound
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = 8 + RoundSeed::INIT_SPACE,
+        seeds = [b"round_seed", (payout_pool.round_id + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_seed: Option<Account<'info, RoundSeed>>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = payout_pool.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Payout quote vault
+    #[account(
+        mut,
+        seeds = [b"payout_quote_vault", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Treasury's quote token account
+    #[account(
+        mut,
+        constraint = treasury_quote_account.mint == payout_pool.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = treasury_quote_account.owner == protocol.treasury @ BoomError::InvalidSweepDestination
+    )]
+    pub treasury_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
 
-#[derive(Accounts)]
-#[instruction(round_id: u64)]
-pub struct StartPresale<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + PresaleRound::INIT_SPACE,
-        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub presale_round: Account<'info, PresaleRound>,
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub caller: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+// ==================== ROUND SEQUENCER CONTEXTS ====================
+
 #[derive(Accounts)]
-pub struct DepositPresale<'info> {
-    #[account(
-        mut,
-        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump
-    )]
-    pub presale_round: Account<'info, PresaleRound>,
+pub struct InitRoundSequencer<'info> {
     #[account(
-        init_if_needed,
-        payer = depositor,
-        space = 8 + UserDeposit::INIT_SPACE,
-        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), depositor.key().as_ref()],
+        init,
+        payer = authority,
+        space = 8 + RoundSequencer::INIT_SPACE,
+        seeds = [b"round_sequencer"],
         bump
     )]
-    pub user_deposit: Account<'info, UserDeposit>,
+    pub sequencer: Account<'info, RoundSequencer>,
+
     #[account(mut)]
-    pub depositor: Signer<'info>,
+    pub authority: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct EndPresaleAndLottery<'info> {
+pub struct UpdateRoundDefaults<'info> {
     #[account(
         mut,
-        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump,
+        seeds = [b"round_sequencer"],
+        bump = sequencer.bump,
         has_one = authority
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub sequencer: Account<'info, RoundSequencer>,
+
     pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct MarkWinner<'info> {
+pub struct CarryOverFees<'info> {
     #[account(
-        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump,
-        has_one = authority
+        mut,
+        seeds = [b"round_sequencer"],
+        bump = sequencer.bump
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub sequencer: Account<'info, RoundSequencer>,
+
     #[account(
-        mut,
-        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), user_deposit.depositor.as_ref()],
-        bump = user_deposit.bump
+        seeds = [b"presale_explosion", pool.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
     )]
-    pub user_deposit: Account<'info, UserDeposit>,
-    pub authority: Signer<'info>,
-}
+    pub presale_explosion: Account<'info, PresaleExplosion>,
 
-#[derive(Accounts)]
-pub struct ClaimRefund<'info> {
     #[account(
         mut,
-        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
     )]
-    pub presale_round: Account<'info, PresaleRound>,
-    
-    /// Explosion tracking - needed to verify trading has started
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: PDA that holds the pool's SOL
     #[account(
-        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
+        mut,
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
     )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
-    
+    pub sol_vault: UncheckedAccount<'info>,
+
+    /// Next round's pool SOL vault
+    /// CHECK: PDA validated by seeds
     #[account(
         mut,
-        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), depositor.key().as_ref()],
-        bump = user_deposit.bump,
-        has_one = depositor
+        seeds = [b"sol_vault", (pool.round_id + 1).to_le_bytes().as_ref()],
+        bump
     )]
-    pub user_deposit: Account<'info, UserDeposit>,
+    pub next_sol_vault: UncheckedAccount<'info>,
+
+    /// Next round's carry-over record
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = 8 + RoundSeed::INIT_SPACE,
+        seeds = [b"round_seed", (pool.round_id + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_seed: Account<'info, RoundSeed>,
+
     #[account(mut)]
-    pub depositor: Signer<'info>,
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimWinnerTokens<'info> {
+pub struct ForwardRoundSeed<'info> {
     #[account(
-        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump
+        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub pool: Box<Account<'info, Pool>>,
 
+    /// CHECK: PDA that holds the stranded SOL
     #[account(
-        seeds = [b"presale_token", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_token.bump,
-        constraint = presale_token.mint == mint.key() @ BoomError::InvalidMint
-    )]
-    pub presale_token: Account<'info, PresaleToken>,
-    
-    /// Explosion tracking - needed to verify trading has started
-    #[account(
-        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
+        mut,
+        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.sol_vault_bump
     )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
+    pub sol_vault: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), winner.key().as_ref()],
-        bump = user_deposit.bump,
-        has_one = depositor @ BoomError::NotAWinner
+        seeds = [b"round_seed", pool.round_id.to_le_bytes().as_ref()],
+        bump = round_seed.bump
     )]
-    pub user_deposit: Account<'info, UserDeposit>,
-
-    /// The token mint
-    #[account(mut)]
-    pub mint: InterfaceAccount<'info, MintInterface>,
+    pub round_seed: Account<'info, RoundSeed>,
 
-    /// PDA mint authority
+    /// Next round's pool SOL vault
     /// CHECK: PDA validated by seeds
     #[account(
-        seeds = [b"mint_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"sol_vault", (pool.round_id + 1).to_le_bytes().as_ref()],
         bump
     )]
-    pub mint_authority: UncheckedAccount<'info>,
+    pub next_sol_vault: UncheckedAccount<'info>,
 
-    /// Winner's token account (must be initialized beforehand or use init_if_needed)
     #[account(
-        mut,
-        token::mint = mint,
-        token::authority = winner,
-        token::token_program = token_program
+        init_if_needed,
+        payer = caller,
+        space = 8 + RoundSeed::INIT_SPACE,
+        seeds = [b"round_seed", (pool.round_id + 1).to_le_bytes().as_ref()],
+        bump
     )]
-    pub winner_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+    pub next_round_seed: Account<'info, RoundSeed>,
 
-    /// CHECK: Validated via has_one
-    pub depositor: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRoundSequencer<'info> {
+    /// CHECK: PDA validated by seeds, may still use the pre-carry-over layout
+    #[account(mut, seeds = [b"round_sequencer"], bump)]
+    pub sequencer: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub winner: Signer<'info>,
+    pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(round_id: u64)]
-pub struct CreatePresaleToken<'info> {
+#[instruction(new_round_id: u64)]
+pub struct AutoStartNextRound<'info> {
     #[account(
-        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump,
-        has_one = authority
+        mut,
+        seeds = [b"round_sequencer"],
+        bump = sequencer.bump
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub sequencer: Account<'info, RoundSequencer>,
+
+    /// Previous round - must have exploded or failed
+    #[account(
+        seeds = [b"presale", (new_round_id - 1).to_le_bytes().as_ref()],
+        bump = previous_presale_round.bump
+    )]
+    pub previous_presale_round: Account<'info, PresaleRound>,
+
+    /// Previous round's explosion account - may not exist if the round failed
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        seeds = [b"presale_explosion", (new_round_id - 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub previous_explosion: UncheckedAccount<'info>,
 
     #[account(
         init,
-        payer = authority,
-        space = 8 + PresaleToken::INIT_SPACE,
-        seeds = [b"presale_token", round_id.to_le_bytes().as_ref()],
+        payer = payer,
+        space = 8 + PresaleRound::INIT_SPACE,
+        seeds = [b"presale", new_round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub presale_token: Account<'info, PresaleToken>,
+    pub new_presale_round: Account<'info, PresaleRound>,
 
-    /// CHECK: Mint account for Token2022, initialized via CPI
-    #[account(mut)]
-    pub mint: Signer<'info>,
+    /// Tombstone of a closed round with the new id - must not exist
+    /// CHECK: PDA validated by seeds, must be empty
+    #[account(
+        seeds = [b"round_tombstone", new_round_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = round_tombstone.data_is_empty() @ BoomError::RoundIdRetired
+    )]
+    pub round_tombstone: UncheckedAccount<'info>,
 
-    /// CHECK: PDA that will be the mint authority - program controls this
+    /// Carry-over collected for the new round, if any
     #[account(
-        seeds = [b"mint_authority", round_id.to_le_bytes().as_ref()],
-        bump
+        seeds = [b"round_seed", new_round_id.to_le_bytes().as_ref()],
+        bump = round_seed.bump
     )]
-    pub mint_authority: UncheckedAccount<'info>,
+    pub round_seed: Option<Account<'info, RoundSeed>>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub payer: Signer<'info>,
 
-    pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(round_id: u64)]
-pub struct RegisterPresaleToken<'info> {
+pub struct AdvanceRound<'info> {
     #[account(
-        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump,
-        has_one = authority
+        seeds = [b"round_sequencer"],
+        bump = sequencer.bump
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub sequencer: Account<'info, RoundSequencer>,
 
+    /// The sequencer's current round
     #[account(
-        init,
-        payer = authority,
-        space = 8 + PresaleToken::INIT_SPACE,
-        seeds = [b"presale_token", round_id.to_le_bytes().as_ref()],
-        bump
+        mut,
+        seeds = [b"presale", sequencer.current_round.to_le_bytes().as_ref()],
+        bump = presale_round.bump
     )]
-    pub presale_token: Account<'info, PresaleToken>,
-
-    /// The externally-created Token2022 mint (with transfer hook)
-    pub mint: InterfaceAccount<'info, MintInterface>,
+    pub presale_round: Account<'info, PresaleRound>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// Required to start the timer of a live round
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", sequencer.current_round.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Option<Account<'info, PresaleExplosion>>,
 
-    pub system_program: Program<'info, System>,
+    /// Anyone can advance the current round
+    pub caller: Signer<'info>,
 }
 
-// NOTE: CreateLiquidityPool context removed - using hybrid approach with Raydium SDK
+// ==================== JACKPOT CONTEXTS ====================
 
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
-pub struct RegisterLp<'info> {
+pub struct InitJackpot<'info> {
     #[account(
         seeds = [b"presale", round_id.to_le_bytes().as_ref()],
         bump = presale_round.bump,
@@ -1840,14 +7735,21 @@ pub struct RegisterLp<'info> {
     )]
     pub presale_round: Account<'info, PresaleRound>,
 
+    #[account(
+        mut,
+        seeds = [b"presale_explosion", round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
+    )]
+    pub presale_explosion: Account<'info, PresaleExplosion>,
+
     #[account(
         init,
         payer = authority,
-        space = 8 + LpInfo::INIT_SPACE,
-        seeds = [b"lp_info", round_id.to_le_bytes().as_ref()],
+        space = 8 + Jackpot::INIT_SPACE,
+        seeds = [b"jackpot", round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub lp_info: Account<'info, LpInfo>,
+    pub jackpot: Box<Account<'info, Jackpot>>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -1855,226 +7757,263 @@ pub struct RegisterLp<'info> {
     pub system_program: Program<'info, System>,
 }
 
-// ==================== CUSTOM AMM CONTEXTS ====================
-
 #[derive(Accounts)]
-#[instruction(round_id: u64)]
-pub struct CreatePool<'info> {
+pub struct ClaimJackpot<'info> {
     #[account(
         mut,
-        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump,
-        has_one = authority
+        seeds = [b"jackpot", jackpot.round_id.to_le_bytes().as_ref()],
+        bump = jackpot.bump
     )]
-    pub presale_round: Box<Account<'info, PresaleRound>>,
+    pub jackpot: Box<Account<'info, Jackpot>>,
 
+    /// Payout pool - its claim deadline applies to jackpot shares too
     #[account(
-        seeds = [b"presale_token", round_id.to_le_bytes().as_ref()],
-        bump = presale_token.bump,
-        constraint = presale_token.mint == mint.key() @ BoomError::InvalidMint
+        seeds = [b"payout_pool", jackpot.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.bump
+    )]
+    pub payout_pool: Box<Account<'info, PayoutPool>>,
+
+    /// Vault holding SOL for payouts
+    /// CHECK: PDA that holds the extracted SOL
+    #[account(
+        mut,
+        seeds = [b"payout_vault", jackpot.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    // === Quote Asset Accounts (SPL quote rounds only) ===
+    /// Quote asset mint
+    #[account(address = jackpot.quote_mint @ BoomError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+
+    /// Payout quote vault
+    #[account(
+        mut,
+        seeds = [b"payout_quote_vault", jackpot.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Winner's quote token account (receives the share)
+    #[account(
+        mut,
+        constraint = winner_quote_account.mint == jackpot.quote_mint @ BoomError::InvalidQuoteAccount,
+        constraint = winner_quote_account.owner == winner.key() @ BoomError::InvalidQuoteAccount
     )]
-    pub presale_token: Box<Account<'info, PresaleToken>>,
+    pub winner_quote_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
 
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
     #[account(
-        init,
-        payer = authority,
-        space = 8 + Pool::INIT_SPACE,
-        seeds = [b"pool", round_id.to_le_bytes().as_ref()],
-        bump
+        seeds = [b"quote_authority", jackpot.round_id.to_le_bytes().as_ref()],
+        bump = jackpot.quote_authority_bump
     )]
-    pub pool: Box<Account<'info, Pool>>,
+    pub quote_authority: Option<UncheckedAccount<'info>>,
 
-    /// The token mint
-    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
 
-    /// Token vault PDA - holds tokens for the pool
+    pub system_program: Program<'info, System>,
+}
+
+// ==================== MOCK RANDOMNESS CONTEXTS ====================
+
+#[derive(Accounts)]
+#[instruction(commitment: [u8; 32])]
+pub struct InitMockRandomness<'info> {
     #[account(
         init,
         payer = authority,
-        token::mint = mint,
-        token::authority = pool,
-        token::token_program = token_program,
-        seeds = [b"token_vault", round_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub token_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
-
-    /// SOL vault PDA - holds SOL for the pool
-    /// CHECK: PDA that holds SOL
-    #[account(
-        mut,
-        seeds = [b"sol_vault", round_id.to_le_bytes().as_ref()],
+        space = 8 + MockRandomness::INIT_SPACE,
+        seeds = [b"mock_randomness", commitment.as_ref()],
         bump
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub randomness: Account<'info, MockRandomness>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Swap<'info> {
+pub struct RevealMockRandomness<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
+        seeds = [b"mock_randomness", randomness.commitment.as_ref()],
+        bump = randomness.bump,
+        has_one = authority
     )]
-    pub pool: Account<'info, Pool>,
+    pub randomness: Account<'info, MockRandomness>,
 
-    /// The token mint
-    #[account(
-        constraint = mint.key() == pool.mint @ BoomError::InvalidMint
-    )]
-    pub mint: InterfaceAccount<'info, MintInterface>,
+    pub authority: Signer<'info>,
+}
 
-    /// Pool's token vault
-    #[account(
-        mut,
-        token::mint = mint,
-        token::authority = pool,
-        constraint = token_vault.key() == pool.token_vault @ BoomError::InvalidVault
-    )]
-    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+// ==================== ACCOUNT CLOSING CONTEXTS ====================
 
-    /// Pool's SOL vault
-    /// CHECK: PDA holding SOL
+#[derive(Accounts)]
+pub struct CloseUserDeposit<'info> {
     #[account(
         mut,
-        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.sol_vault_bump
+        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub presale_round: Account<'info, PresaleRound>,
 
-    /// User's token account
     #[account(
         mut,
-        token::mint = mint,
-        token::authority = user
+        close = depositor,
+        seeds = [b"deposit", presale_round.round_id.to_le_bytes().as_ref(), depositor.key().as_ref()],
+        bump = user_deposit.bump,
+        has_one = depositor
     )]
-    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+    pub user_deposit: Account<'info, UserDeposit>,
 
     #[account(mut)]
-    pub user: Signer<'info>,
-
-    pub token_program: Program<'info, Token2022>,
-    pub system_program: Program<'info, System>,
-
-    // === Transfer Hook Accounts ===
-    /// The transfer hook program
-    /// CHECK: Hook program ID
-    pub hook_program: UncheckedAccount<'info>,
-    
-    /// Extra account metas PDA for the mint
-    /// CHECK: PDA derived from ["extra-account-metas", mint]
-    pub extra_account_metas: UncheckedAccount<'info>,
-    
-    /// Hook config PDA
-    /// CHECK: PDA derived from ["hook_config"] in hook program
-    pub hook_config: UncheckedAccount<'info>,
-    
-    /// Hook whitelist PDA for this mint
-    /// CHECK: PDA derived from ["whitelist", mint] in hook program
-    pub hook_whitelist: UncheckedAccount<'info>,
+    pub depositor: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct GetSwapQuote<'info> {
+pub struct CloseTraderState<'info> {
+    /// Explosion tracking - may not exist if the round failed
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
     #[account(
-        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
+        seeds = [b"presale_explosion", trader_state.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub pool: Account<'info, Pool>,
+    pub presale_explosion: UncheckedAccount<'info>,
+
+    /// Presale round - failed or closed rounds release their trader state too
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
+    #[account(
+        seeds = [b"presale", trader_state.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_round: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"trader", trader_state.round_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = trader_state.bump,
+        has_one = user
+    )]
+    pub trader_state: Account<'info, TraderState>,
+
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct SyncPoolReserves<'info> {
+pub struct CloseWinnerVesting<'info> {
     #[account(
-        mut,
-        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
+        seeds = [b"presale", winner_vesting.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.bump
     )]
-    pub pool: Account<'info, Pool>,
+    pub presale_round: Account<'info, PresaleRound>,
 
-    /// Token vault PDA
     #[account(
-        token::mint = pool.mint,
-        seeds = [b"token_vault", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.token_vault_bump
+        seeds = [b"presale_explosion", winner_vesting.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
     )]
-    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+    pub presale_explosion: Account<'info, PresaleExplosion>,
 
-    /// SOL vault PDA
-    /// CHECK: PDA holding SOL
     #[account(
-        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.sol_vault_bump
+        mut,
+        close = winner,
+        seeds = [b"vesting", winner_vesting.round_id.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump = winner_vesting.bump,
+        has_one = winner
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub winner_vesting: Account<'info, WinnerVesting>,
+
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
 }
 
-/// Accounts for atomic sell (user transfers tokens first, then calls this)
 #[derive(Accounts)]
-pub struct SwapAtomicSell<'info> {
+pub struct ClosePayoutPool<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
+        close = rent_payer,
+        seeds = [b"payout_pool", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.bump,
+        has_one = rent_payer
     )]
-    pub pool: Account<'info, Pool>,
+    pub payout_pool: Account<'info, PayoutPool>,
 
-    /// Pool's token vault - verify balance increased
+    /// Jackpot - must already be closed (or never created)
+    /// CHECK: PDA validated by seeds, must be empty
     #[account(
-        token::mint = pool.mint,
-        constraint = token_vault.key() == pool.token_vault @ BoomError::InvalidVault
+        seeds = [b"jackpot", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+    pub jackpot: UncheckedAccount<'info>,
 
-    /// Pool's SOL vault - SOL sent from here
-    /// CHECK: PDA holding SOL
+    /// SOL payout vault - swept to the rent payer
+    /// CHECK: PDA validated by seeds
     #[account(
         mut,
-        seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.sol_vault_bump
+        seeds = [b"payout_vault", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub payout_vault: UncheckedAccount<'info>,
 
-    /// User receiving SOL
+    /// CHECK: Validated via has_one, receives the rent
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// Payout quote vault (SPL quote rounds only)
+    #[account(
+        mut,
+        seeds = [b"payout_quote_vault", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", payout_pool.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
 
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
-/// Accounts for atomic buy (SOL in, tokens out via CPI with remaining_accounts)
 #[derive(Accounts)]
-pub struct SwapAtomicBuy<'info> {
+pub struct ClosePool<'info> {
     #[account(
         mut,
+        close = rent_payer,
         seeds = [b"pool", pool.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
+        bump = pool.bump,
+        has_one = rent_payer
     )]
-    pub pool: Account<'info, Pool>,
+    pub pool: Box<Account<'info, Pool>>,
 
-    /// The token mint
     #[account(
-        constraint = mint.key() == pool.mint @ BoomError::InvalidMint
+        seeds = [b"presale_explosion", pool.round_id.to_le_bytes().as_ref()],
+        bump = presale_explosion.bump
     )]
-    pub mint: InterfaceAccount<'info, MintInterface>,
+    pub presale_explosion: Box<Account<'info, PresaleExplosion>>,
 
-    /// Pool's token vault
+    /// Pool's token vault - emptied by unwind_lp
     #[account(
         mut,
-        token::mint = mint,
-        token::authority = pool,
-        constraint = token_vault.key() == pool.token_vault @ BoomError::InvalidVault
+        address = pool.token_vault @ BoomError::InvalidVault
     )]
-    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
 
-    /// Pool's SOL vault
-    /// CHECK: PDA holding SOL
+    /// Pool's SOL vault - swept to the rent payer
+    /// CHECK: PDA validated by seeds
     #[account(
         mut,
         seeds = [b"sol_vault", pool.round_id.to_le_bytes().as_ref()],
@@ -2082,340 +8021,313 @@ pub struct SwapAtomicBuy<'info> {
     )]
     pub sol_vault: UncheckedAccount<'info>,
 
-    /// User's token account (receives tokens)
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// Pool quote vault (SPL quote rounds only)
     #[account(
         mut,
-        token::mint = mint,
-        token::authority = user
+        seeds = [b"pool_quote_vault", pool.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+    pub pool_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
 
-    /// User sending SOL
-    #[account(mut)]
-    pub user: Signer<'info>,
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"quote_authority", pool.round_id.to_le_bytes().as_ref()],
+        bump = pool.quote_authority_bump
+    )]
+    pub quote_authority: Option<UncheckedAccount<'info>>,
 
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
-
-    // === Transfer Hook Accounts (passed via remaining_accounts in CPI) ===
-    /// The transfer hook program
-    /// CHECK: Hook program ID
-    pub hook_program: UncheckedAccount<'info>,
-    
-    /// Extra account metas PDA for the mint
-    /// CHECK: PDA derived from ["extra-account-metas", mint]
-    pub extra_account_metas: UncheckedAccount<'info>,
-    
-    /// Hook config PDA
-    /// CHECK: PDA derived from ["hook_config"] in hook program
-    pub hook_config: UncheckedAccount<'info>,
-    
-    /// Hook whitelist PDA for this mint
-    /// CHECK: PDA derived from ["whitelist", mint] in hook program
-    pub hook_whitelist: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
-pub struct DepositPoolTokens<'info> {
+pub struct ClosePresaleRound<'info> {
     #[account(
+        mut,
+        close = rent_payer,
         seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
         bump = presale_round.bump,
-        has_one = authority
+        has_one = rent_payer
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub presale_round: Box<Account<'info, PresaleRound>>,
 
+    /// Explosion tracking - may not exist if the round failed
+    /// CHECK: PDA validated by seeds, deserialized in the handler when initialized
     #[account(
-        seeds = [b"presale_token", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_token.bump,
-        constraint = presale_token.mint == mint.key() @ BoomError::InvalidMint
+        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub presale_token: Account<'info, PresaleToken>,
+    pub presale_explosion: UncheckedAccount<'info>,
 
-    /// The token mint
-    pub mint: InterfaceAccount<'info, MintInterface>,
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
 
-    /// Token vault PDA
+    /// Presale quote vault (SPL quote rounds only)
     #[account(
         mut,
-        token::mint = mint,
-        seeds = [b"token_vault", presale_round.round_id.to_le_bytes().as_ref()],
+        seeds = [b"quote_vault", presale_round.round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
-
-    /// Authority's token account
-    #[account(
-        mut,
-        token::mint = mint,
-        token::authority = authority
-    )]
-    pub authority_token_account: InterfaceAccount<'info, TokenAccountInterface>,
-
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    pub token_program: Program<'info, Token2022>,
-}
-
-// ==================== PRESALE EXPLOSION CONTEXTS ====================
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
 
-#[derive(Accounts)]
-#[instruction(round_id: u64)]
-pub struct InitPresaleExplosion<'info> {
+    /// Quote vault authority PDA
+    /// CHECK: PDA validated by seeds
     #[account(
-        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump,
-        has_one = authority
+        seeds = [b"quote_authority", presale_round.round_id.to_le_bytes().as_ref()],
+        bump = presale_round.quote_authority_bump
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub quote_authority: Option<UncheckedAccount<'info>>,
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + PresaleExplosion::INIT_SPACE,
-        seeds = [b"presale_explosion", round_id.to_le_bytes().as_ref()],
+    /// Tombstone left at the round id - created in the handler
+    /// CHECK: PDA validated by seeds
+    #[account(
+        mut,
+        seeds = [b"round_tombstone", presale_round.round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
-
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    pub round_tombstone: UncheckedAccount<'info>,
 
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct StartExplosionTimer<'info> {
+pub struct ClosePresaleToken<'info> {
     #[account(
         mut,
-        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
+        close = rent_payer,
+        seeds = [b"presale_token", presale_token.round_id.to_le_bytes().as_ref()],
+        bump = presale_token.bump,
+        has_one = rent_payer
     )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
+    pub presale_token: Account<'info, PresaleToken>,
 
-    /// LP must be registered before starting timer
+    /// Presale round - must already be closed
+    /// CHECK: PDA validated by seeds, must be empty
     #[account(
-        seeds = [b"lp_info", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = lp_info.bump
+        seeds = [b"presale", presale_token.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub lp_info: Account<'info, LpInfo>,
+    pub presale_round: UncheckedAccount<'info>,
 
-    /// Authority that can start the timer
-    pub authority: Signer<'info>,
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct TriggerPresaleExplosion<'info> {
+pub struct CloseJackpot<'info> {
     #[account(
         mut,
-        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
+        close = rent_payer,
+        seeds = [b"jackpot", jackpot.round_id.to_le_bytes().as_ref()],
+        bump = jackpot.bump,
+        has_one = rent_payer
     )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
-
-    /// Authority or oracle that can trigger explosion
-    pub trigger_authority: Signer<'info>,
-}
+    pub jackpot: Box<Account<'info, Jackpot>>,
 
-#[derive(Accounts)]
-pub struct TriggerExplosionWithPyth<'info> {
+    /// Payout pool - pass once unclaimed shares have been swept
     #[account(
-        mut,
-        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
+        seeds = [b"payout_pool", jackpot.round_id.to_le_bytes().as_ref()],
+        bump = payout_pool.bump
     )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
+    pub payout_pool: Option<Box<Account<'info, PayoutPool>>>,
 
+    /// Presale round - must be closed if the jackpot was never funded
+    /// CHECK: PDA validated by seeds
     #[account(
-        seeds = [b"presale_token", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = presale_token.bump
+        seeds = [b"presale", jackpot.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub presale_token: Account<'info, PresaleToken>,
+    pub presale_round: UncheckedAccount<'info>,
+
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
 
+#[derive(Accounts)]
+pub struct CloseLpInfo<'info> {
     #[account(
-        seeds = [b"lp_info", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = lp_info.bump
+        mut,
+        close = rent_payer,
+        seeds = [b"lp_info", lp_info.round_id.to_le_bytes().as_ref()],
+        bump = lp_info.bump,
+        has_one = rent_payer
     )]
     pub lp_info: Account<'info, LpInfo>,
 
-    /// Pyth price update account
-    pub price_update: Account<'info, PriceUpdateV2>,
+    /// Presale round - must already be closed
+    /// CHECK: PDA validated by seeds, must be empty
+    #[account(
+        seeds = [b"presale", lp_info.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub presale_round: UncheckedAccount<'info>,
 
-    /// Anyone can trigger if price threshold met
-    pub caller: Signer<'info>,
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct TriggerPresaleExplosionTime<'info> {
+pub struct ClosePresaleExplosion<'info> {
     #[account(
         mut,
+        close = rent_payer,
         seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
+        bump = presale_explosion.bump,
+        has_one = rent_payer
     )]
     pub presale_explosion: Account<'info, PresaleExplosion>,
 
-    /// Anyone can trigger time-based explosion
-    pub caller: Signer<'info>,
-}
-
-#[derive(Accounts)]
-#[instruction(total_sol_extracted: u64, remaining_token_supply: u64)]
-pub struct UnwindLp<'info> {
+    /// Presale round - must already be closed
+    /// CHECK: PDA validated by seeds, must be empty
     #[account(
-        seeds = [b"presale", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_round.bump,
-        has_one = authority
+        seeds = [b"presale", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub presale_round: Account<'info, PresaleRound>,
+    pub presale_round: UncheckedAccount<'info>,
 
+    /// AMM pool - must already be closed (or never created)
+    /// CHECK: PDA validated by seeds, must be empty
     #[account(
-        mut,
-        seeds = [b"presale_explosion", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
+        seeds = [b"pool", presale_explosion.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
+    pub pool: UncheckedAccount<'info>,
 
+    /// Payout pool - must already be closed (or never created)
+    /// CHECK: PDA validated by seeds, must be empty
     #[account(
-        init,
-        payer = authority,
-        space = 8 + PayoutPool::INIT_SPACE,
-        seeds = [b"payout_pool", presale_round.round_id.to_le_bytes().as_ref()],
+        seeds = [b"payout_pool", presale_explosion.round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub payout_pool: Account<'info, PayoutPool>,
+    pub payout_pool: UncheckedAccount<'info>,
 
-    /// The AMM pool (needed to get token vault info and as burn authority)
-    #[account(
-        seeds = [b"pool", presale_round.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
-    )]
-    pub pool: Account<'info, Pool>,
+    /// CHECK: Validated via has_one, receives the rent
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
 
-    /// Pool's token vault - tokens here will be burned
+#[derive(Accounts)]
+pub struct CloseRoundSeed<'info> {
     #[account(
         mut,
-        address = pool.token_vault
+        close = rent_payer,
+        seeds = [b"round_seed", round_seed.round_id.to_le_bytes().as_ref()],
+        bump = round_seed.bump,
+        has_one = rent_payer
     )]
-    pub token_vault: InterfaceAccount<'info, TokenAccountInterface>,
+    pub round_seed: Account<'info, RoundSeed>,
 
-    /// Token mint - to burn LP tokens and get remaining supply
+    /// Pool of the seeded round - must exist
     #[account(
-        mut,
-        address = pool.mint
+        seeds = [b"pool", round_seed.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
     )]
-    pub mint: InterfaceAccount<'info, MintInterface>,
+    pub pool: Box<Account<'info, Pool>>,
 
+    /// CHECK: Validated via has_one, receives the rent
     #[account(mut)]
-    pub authority: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token2022>,
+    pub rent_payer: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimExplosionPayout<'info> {
-    #[account(
-        seeds = [b"presale_explosion", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = presale_explosion.bump
-    )]
-    pub presale_explosion: Account<'info, PresaleExplosion>,
-
+pub struct CloseLaunch<'info> {
     #[account(
         mut,
-        seeds = [b"payout_pool", presale_explosion.round_id.to_le_bytes().as_ref()],
-        bump = payout_pool.bump
+        close = rent_payer,
+        seeds = [b"launch", launch.mint.as_ref()],
+        bump = launch.bump,
+        has_one = rent_payer
     )]
-    pub payout_pool: Account<'info, PayoutPool>,
+    pub launch: Account<'info, Launch>,
 
-    /// Vault holding SOL for payouts
-    /// CHECK: PDA that holds the extracted SOL
+    /// Presale round - must already be closed (presale launches only)
+    /// CHECK: PDA validated by seeds, must be empty
     #[account(
-        mut,
-        seeds = [b"payout_vault", presale_explosion.round_id.to_le_bytes().as_ref()],
+        seeds = [b"presale", launch.round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub payout_vault: UncheckedAccount<'info>,
+    pub presale_round: UncheckedAccount<'info>,
 
-    /// User's token account
+    /// Explosion record - must already be closed (presale launches only)
+    /// CHECK: PDA validated by seeds, must be empty
     #[account(
-        mut,
-        token::authority = user,
+        seeds = [b"presale_explosion", launch.round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub user_token_account: InterfaceAccount<'info, TokenAccountInterface>,
-
-    /// The token mint
-    #[account(mut)]
-    pub mint: InterfaceAccount<'info, MintInterface>,
+    pub presale_explosion: UncheckedAccount<'info>,
 
+    /// CHECK: Validated via has_one, receives the rent
     #[account(mut)]
-    pub user: Signer<'info>,
-
-    pub token_program: Program<'info, Token2022>,
-    pub system_program: Program<'info, System>,
+    pub rent_payer: UncheckedAccount<'info>,
 }
 
-// ==================== ROUND SEQUENCER CONTEXTS ====================
+// ==================== ACCOUNT MIGRATION CONTEXTS ====================
 
 #[derive(Accounts)]
-pub struct InitRoundSequencer<'info> {
+#[instruction(round_id: u64)]
+pub struct MigrateRoundAccount<'info> {
+    /// Round account to grow - owner, type and address are checked in the handler
+    /// CHECK: May still use its first-deployment layout
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+
+    /// Round whose authority signs and pays for the migration
+    /// CHECK: PDA validated by seeds, may still use its first-deployment layout
     #[account(
-        init,
-        payer = authority,
-        space = 8 + RoundSequencer::INIT_SPACE,
-        seeds = [b"round_sequencer"],
+        seeds = [b"presale", round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub sequencer: Account<'info, RoundSequencer>,
-
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub presale_round: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-pub struct UpdateRoundDefaults<'info> {
+    // === Round records (place a migrated presale round in its stage) ===
+    /// CHECK: PDA validated by seeds, may not exist
     #[account(
-        mut,
-        seeds = [b"round_sequencer"],
-        bump = sequencer.bump,
-        has_one = authority
+        seeds = [b"presale_token", round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub sequencer: Account<'info, RoundSequencer>,
+    pub presale_token: UncheckedAccount<'info>,
 
-    pub authority: Signer<'info>,
-}
+    /// CHECK: PDA validated by seeds, may not exist
+    #[account(
+        seeds = [b"pool", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-#[instruction(new_round_id: u64)]
-pub struct AutoStartNextRound<'info> {
+    /// CHECK: PDA validated by seeds, may not exist
     #[account(
-        mut,
-        seeds = [b"round_sequencer"],
-        bump = sequencer.bump
+        seeds = [b"lp_info", round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub sequencer: Account<'info, RoundSequencer>,
+    pub lp_info: UncheckedAccount<'info>,
 
-    /// Previous round's explosion account - must be exploded
+    /// CHECK: PDA validated by seeds, may not exist
     #[account(
-        seeds = [b"presale_explosion", (new_round_id - 1).to_le_bytes().as_ref()],
-        bump = previous_explosion.bump,
-        constraint = previous_explosion.is_exploded @ BoomError::NotExploded
+        seeds = [b"presale_explosion", round_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub previous_explosion: Account<'info, PresaleExplosion>,
+    pub presale_explosion: UncheckedAccount<'info>,
 
+    /// CHECK: PDA validated by seeds, may not exist
     #[account(
-        init,
-        payer = payer,
-        space = 8 + PresaleRound::INIT_SPACE,
-        seeds = [b"presale", new_round_id.to_le_bytes().as_ref()],
+        seeds = [b"payout_pool", round_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub new_presale_round: Account<'info, PresaleRound>,
+    pub payout_pool: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -2434,6 +8346,17 @@ pub struct Protocol {
     pub bump: u8,
 }
 
+impl Protocol {
+    /// Revealed and committed caps must fall inside the protocol bounds
+    fn check_cap(&self, cap: u64) -> Result<()> {
+        require!(
+            cap >= self.min_cap && cap <= self.max_cap,
+            BoomError::CapOutOfBounds
+        );
+        Ok(())
+    }
+}
+
 #[account]
 pub struct BoomToken {
     pub mint: Pubkey,
@@ -2451,12 +8374,84 @@ pub struct BoomToken {
     pub bump: u8,
 }
 
+/// Unified launch record - one per mint, for both presale rounds and legacy BoomTokens
+#[account]
+#[derive(InitSpace)]
+pub struct Launch {
+    pub mint: Pubkey,               // 32
+    pub kind: LaunchKind,           // 1
+    pub status: LaunchStatus,       // 1
+    pub round_id: u64,              // 8 - Presale round (0 for legacy launches)
+    pub presale_round: Pubkey,      // 32
+    pub presale_token: Pubkey,      // 32
+    pub presale_explosion: Pubkey,  // 32 - Set when the round explodes
+    pub pool: Pubkey,               // 32 - Set when the pool is created
+    pub payout_pool: Pubkey,        // 32 - Set when the LP is unwound
+    pub boom_token: Pubkey,         // 32 - Legacy BoomToken account
+    pub created_at: i64,            // 8
+    pub exploded_at: i64,           // 8
+    pub bump: u8,                   // 1
+    pub rent_payer: Pubkey,         // 32 - Receives the rent when the launch is closed
+}
+
+impl Launch {
+    fn init_presale(
+        &mut self,
+        mint: Pubkey,
+        round_id: u64,
+        presale_round: Pubkey,
+        presale_token: Pubkey,
+        rent_payer: Pubkey,
+        bump: u8,
+    ) -> Result<()> {
+        self.mint = mint;
+        self.kind = LaunchKind::Presale;
+        self.status = LaunchStatus::Presale;
+        self.round_id = round_id;
+        self.presale_round = presale_round;
+        self.presale_token = presale_token;
+        self.created_at = Clock::get()?.unix_timestamp;
+        self.bump = bump;
+        self.rent_payer = rent_payer;
+        Ok(())
+    }
+
+    fn init_legacy(&mut self, boom_token: &BoomToken, boom_token_key: Pubkey, rent_payer: Pubkey, bump: u8) {
+        self.mint = boom_token.mint;
+        self.kind = LaunchKind::Legacy;
+        self.status = if boom_token.is_exploded {
+            LaunchStatus::Exploded
+        } else {
+            LaunchStatus::Trading
+        };
+        self.boom_token = boom_token_key;
+        self.created_at = boom_token.created_at;
+        self.exploded_at = boom_token.explosion_time;
+        self.bump = bump;
+        self.rent_payer = rent_payer;
+    }
+
+    fn mark_trading(&mut self, pool: Pubkey) {
+        self.pool = pool;
+        self.status = LaunchStatus::Trading;
+    }
+
+    fn mark_exploded(&mut self, now: i64) {
+        self.status = LaunchStatus::Exploded;
+        self.exploded_at = now;
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum ExplosionReason {
     #[default]
     None,
     CapHit,      // Secret market cap was reached
     TimeLimit,   // Time ran out
+    VolumeThreshold, // Cumulative pool volume reached
+    HolderThreshold, // Enough distinct buyers
+    Drawdown,    // Price fell far enough below its all-time high
+    RandomSlot,  // Per-slot random roll hit
 }
 
 // ==================== PRESALE ACCOUNTS ====================
@@ -2475,6 +8470,178 @@ pub struct PresaleRound {
     pub total_depositors: u32,      // 4
     pub is_finalized: bool,         // 1
     pub bump: u8,                   // 1
+    pub allocation: AllocationConfig, // 17 - How winners' tokens are allocated
+    pub total_capped_deposited: u64, // 8 - Sum of deposits capped at allocation_cap
+    pub winners_committed: u64,     // 8 - Sum of lottery winners' purchase cost
+    pub quote_mint: Pubkey,         // 32 - Quote asset mint (default = native SOL)
+    pub quote_authority_bump: u8,   // 1 - Bump of the quote vault authority PDA
+    pub whitelist_root: [u8; 32],   // 32 - Merkle root of allowed wallets (zero = open)
+    pub withdraw_penalty_bps: u16,  // 2 - Early-withdraw penalty
+    pub penalties_collected: u64,   // 8 - Penalties kept from early withdrawals
+    pub launch_grace_period: i64,   // 8 - Time after end_time to start trading
+    pub launch_failed: bool,        // 1 - Round failed, full refunds open
+    pub soft_cap: u64,              // 8 - Minimum raise for the round to launch (0 = none)
+    pub hard_cap: u64,              // 8 - Maximum raise, deposits stop here (0 = none)
+    pub anti_snipe: AntiSnipeConfig, // 24 - Late-deposit end_time extension
+    pub total_extension: i64,       // 8 - Seconds end_time has been extended so far
+    pub vesting: VestingConfig,     // 17 - Winner token vesting schedule
+    pub vesting_allocated: u64,     // 8 - Tokens allocated to vesting winners
+    pub vesting_minted: u64,        // 8 - Vested tokens minted so far
+    pub settled_deposits: u32,      // 4 - Settled deposits closed via close_user_deposit
+    pub rent_payer: Pubkey,         // 32 - Receives rent when the round is closed
+    pub stage: RoundStage,          // 1 - Position in the round pipeline
+    pub claim_window: i64,          // 8 - Payout claim window after unwind (0 = no deadline)
+    pub winner_count: u32,          // 4 - Lottery winners marked via mark_winner
+    pub allocation_claimed: u64,    // 8 - Winner allocation claimed before the explosion
+}
+
+impl PresaleRound {
+    /// Fail unless the round is at one of `stages`
+    pub fn require_stage(&self, stages: &[RoundStage]) -> Result<()> {
+        require!(stages.contains(&self.stage), BoomError::InvalidRoundStage);
+        Ok(())
+    }
+
+    /// Move the round to `to` from one of the `from` stages
+    pub fn advance_stage(&mut self, from: &[RoundStage], to: RoundStage) -> Result<()> {
+        self.require_stage(from)?;
+        if self.stage != to {
+            self.stage = to;
+            emit!(RoundStageChanged {
+                round_id: self.round_id,
+                stage: to,
+            });
+        }
+        Ok(())
+    }
+
+    /// Deposit has nothing left to claim or refund
+    pub fn deposit_settled(&self, deposit: &UserDeposit) -> Result<bool> {
+        if deposit.amount == 0 {
+            return Ok(true);
+        }
+        if !deposit.claimed {
+            return Ok(false);
+        }
+        // Pro-rata deposits also carry a separately claimed refund
+        Ok(self.allocation.mode == AllocationMode::FixedPerWinner
+            || deposit.refund_claimed
+            || self.unfilled_amount(deposit.amount)? == 0)
+    }
+
+    /// Tokens owed to winners across the round
+    /// Pro-rata claims round down per deposit, so this can exceed their sum by dust.
+    pub fn winner_allocation(&self, tokens_per_winner: u64) -> Result<u64> {
+        let allocation = match self.allocation.mode {
+            AllocationMode::FixedPerWinner => (tokens_per_winner as u128)
+                .checked_mul(self.winner_count as u128)
+                .ok_or(BoomError::Overflow)?,
+            AllocationMode::ProRata | AllocationMode::CappedProRata => {
+                let round_allocation = (tokens_per_winner as u128)
+                    .checked_mul(self.lottery_spots as u128)
+                    .ok_or(BoomError::Overflow)?;
+                let filled = self.committed_sol().saturating_sub(self.penalties_collected);
+                round_allocation
+                    .checked_mul(filled as u128)
+                    .ok_or(BoomError::Overflow)?
+                    .checked_div(self.allocation.raise_target as u128)
+                    .ok_or(BoomError::Overflow)?
+            }
+        };
+        u64::try_from(allocation).map_err(|_| error!(BoomError::Overflow))
+    }
+
+    /// Winner allocation nobody claimed before the explosion
+    /// Counted in the payout supply so late claimers take a payout share instead.
+    pub fn unclaimed_allocation(&self, tokens_per_winner: u64) -> Result<u64> {
+        Ok(self
+            .winner_allocation(tokens_per_winner)?
+            .saturating_sub(self.allocation_claimed))
+    }
+
+    /// Tokens allocated to vesting winners that have not been minted yet
+    pub fn unvested_supply(&self) -> u64 {
+        self.vesting_allocated.saturating_sub(self.vesting_minted)
+    }
+
+    /// Round failed: soft cap missed, or trading did not start within the grace period
+    /// Once a pool holds the committed SOL the round can no longer fail - refunds
+    /// would otherwise be paid out of losing depositors' share of the presale.
+    pub fn is_failed(&self, now: i64) -> bool {
+        if self.pool_live() {
+            return false;
+        }
+        self.launch_failed
+            || self.soft_cap_missed(now)
+            || now > self.end_time.saturating_add(self.launch_grace_period)
+    }
+
+    /// Payout claim deadline for an LP unwound at `now` (0 = no deadline)
+    pub fn claim_deadline(&self, now: i64) -> Result<i64> {
+        if self.claim_window == 0 {
+            return Ok(0);
+        }
+        Ok(now.checked_add(self.claim_window).ok_or(BoomError::Overflow)?)
+    }
+
+    /// Losing deposits can be refunded: the pool holds the winners' SOL or the round failed
+    /// Refunds do not wait for the explosion timer, which the authority may never start.
+    pub fn refunds_open(&self, now: i64) -> bool {
+        self.pool_live() || self.is_failed(now)
+    }
+
+    /// A trading pool has been created or registered for the round
+    pub fn pool_live(&self) -> bool {
+        matches!(
+            self.stage,
+            RoundStage::PoolLive | RoundStage::Exploded | RoundStage::Settled
+        )
+    }
+
+    /// Round ended without reaching its soft cap
+    pub fn soft_cap_missed(&self, now: i64) -> bool {
+        self.soft_cap > 0 && now >= self.end_time && self.total_deposited < self.soft_cap
+    }
+
+    /// SOL committed to the pool - everything else is owed back as refunds
+    /// Early-withdraw penalties are never refunded, so they always go to the pool.
+    pub fn committed_sol(&self) -> u64 {
+        let committed = match self.allocation.mode {
+            AllocationMode::FixedPerWinner => self.winners_committed,
+            AllocationMode::ProRata => self.total_deposited.min(self.allocation.raise_target),
+            AllocationMode::CappedProRata => {
+                self.total_capped_deposited.min(self.allocation.raise_target)
+            }
+        };
+        committed.saturating_add(self.penalties_collected)
+    }
+
+    /// Portion of a pro-rata deposit that is not filled and gets refunded
+    /// Rounded down so total refunds never exceed the uncommitted SOL.
+    pub fn unfilled_amount(&self, deposit: u64) -> Result<u64> {
+        let (eligible, eligible_total) = match self.allocation.mode {
+            AllocationMode::FixedPerWinner => return Ok(0),
+            AllocationMode::ProRata => (deposit, self.total_deposited),
+            AllocationMode::CappedProRata => (
+                deposit.min(self.allocation.allocation_cap),
+                self.total_capped_deposited,
+            ),
+        };
+        let over_cap = deposit - eligible;
+
+        let target = self.allocation.raise_target;
+        let oversubscribed = if eligible_total > target {
+            (eligible as u128)
+                .checked_mul((eligible_total - target) as u128)
+                .ok_or(BoomError::Overflow)?
+                .checked_div(eligible_total as u128)
+                .ok_or(BoomError::Overflow)? as u64
+        } else {
+            0
+        };
+
+        Ok(over_cap + oversubscribed)
+    }
 }
 
 #[account]
@@ -2487,6 +8654,7 @@ pub struct UserDeposit {
     pub is_winner: bool,            // 1
     pub claimed: bool,              // 1
     pub bump: u8,                   // 1
+    pub refund_claimed: bool,       // 1 - pro-rata remainder refunded
 }
 
 #[account]
@@ -2497,13 +8665,32 @@ pub struct PresaleToken {
     pub total_supply: u64,          // 8
     pub tokens_per_winner: u64,     // 8
     pub bump: u8,                   // 1
+    pub launch_price: u64,          // 8 - lamports per whole token for winners (0 = full deposit)
+    pub rent_payer: Pubkey,         // 32 - Receives rent when closed
+}
+
+impl PresaleToken {
+    /// What a lottery winner pays for tokens_per_winner at the launch price
+    /// The deposit must cover it; without a launch price the whole deposit is the cost.
+    pub fn winner_purchase_cost(&self, deposit: u64) -> Result<u64> {
+        if self.launch_price == 0 {
+            return Ok(deposit);
+        }
+        let cost = (self.tokens_per_winner as u128)
+            .checked_mul(self.launch_price as u128)
+            .ok_or(BoomError::Overflow)?
+            / 1_000_000_000;
+        let cost = u64::try_from(cost).map_err(|_| BoomError::Overflow)?;
+        require!(deposit >= cost, BoomError::DepositBelowPurchaseCost);
+        Ok(cost)
+    }
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct PresaleExplosion {
     pub round_id: u64,              // 8
-    pub cap_hash: [u8; 32],         // 32 - SHA256 of secret cap
+    pub cap_hash: [u8; 32],         // 32 - SHA256 of secret cap and salt
     pub revealed_cap: u64,          // 8 - revealed after explosion
     pub explosion_deadline: i64,    // 8 - time limit
     pub is_exploded: bool,          // 1
@@ -2511,6 +8698,53 @@ pub struct PresaleExplosion {
     pub explosion_reason: ExplosionReason, // 1
     pub total_sol_for_payout: u64,  // 8 - SOL collected for distribution
     pub bump: u8,                   // 1
+    pub timer_started_at: i64,      // 8 - when start_explosion_timer was called
+    pub rent_payer: Pubkey,         // 32 - Receives rent when closed
+    pub cap_range_min: u64,         // 8 - Publicly disclosed lower bound of the secret cap
+    pub cap_range_max: u64,         // 8 - Publicly disclosed upper bound (0 = no range disclosed)
+    pub puzzle_seed: [u8; 32],      // 32 - Time-lock puzzle start value
+    pub puzzle_iterations: u64,     // 8 - Sequential sha256 rounds (0 = no puzzle)
+    pub puzzle_ciphertext: u64,     // 8 - Cap XOR first 8 bytes of the puzzle key
+    pub randomness_account: Pubkey, // 32 - Randomness provider for derived caps (default = none)
+    pub triggers: ExplosionTriggerConfig, // 26 - Extra explosion triggers
+    pub last_random_slot: u64,      // 8 - Slot the last random roll was committed
+    pub heat: HeatModeConfig,       // 24 - Buy-driven timer extension
+    pub total_heat_extension: i64,  // 8 - Seconds added to the deadline by heat mode
+    pub jackpot_enabled: bool,      // 1 - Buys must record into the Jackpot account
+    pub puzzle_key_hash: [u8; 32],  // 32 - SHA256 of the solved puzzle key
+    pub randomness_seed_slot: u64,  // 8 - Provider commit slot bound with randomness_account
+    pub roll_randomness: Pubkey,    // 32 - Randomness account of the pending roll (default = none)
+    pub roll_seed_slot: u64,        // 8 - Provider commit slot bound with roll_randomness
+}
+
+/// Commit-reveal randomness used in place of a VRF in tests
+#[account]
+#[derive(InitSpace)]
+pub struct MockRandomness {
+    pub authority: Pubkey,          // 32
+    pub commitment: [u8; 32],       // 32 - sha256 of the value
+    pub value: [u8; 32],            // 32 - Zero until revealed
+    pub revealed: bool,             // 1
+    pub bump: u8,                   // 1
+}
+
+impl PresaleExplosion {
+    /// Check a cap reveal against the salted cap hash and the disclosed range
+    fn verify_cap_reveal(&self, cap: u64, salt: &[u8; 32]) -> Result<()> {
+        require!(cap_commitment(cap, salt) == self.cap_hash, BoomError::InvalidCapReveal);
+        self.check_cap_range(cap)
+    }
+
+    /// Reject reveals outside the range disclosed via commit_cap_range
+    fn check_cap_range(&self, cap: u64) -> Result<()> {
+        if self.cap_range_max > 0 {
+            require!(
+                cap >= self.cap_range_min && cap <= self.cap_range_max,
+                BoomError::CapOutOfCommittedRange
+            );
+        }
+        Ok(())
+    }
 }
 
 #[account]
@@ -2523,6 +8757,7 @@ pub struct LpInfo {
     pub vault_b: Pubkey,            // 32 (Token vault)
     pub registered_at: i64,         // 8
     pub bump: u8,                   // 1
+    pub rent_payer: Pubkey,         // 32 - Receives rent when closed
 }
 
 #[account]
@@ -2533,6 +8768,50 @@ pub struct PayoutPool {
     pub remaining_supply: u64,      // 8 - Token supply after LP burn
     pub claimed_count: u32,         // 4 - Number of claims made
     pub bump: u8,                   // 1
+    pub quote_mint: Pubkey,         // 32 - Quote asset paid out (default = native SOL)
+    pub quote_authority_bump: u8,   // 1 - Bump of the quote vault authority PDA
+    pub mint: Pubkey,               // 32 - Round token burned for payouts
+    pub claimed_sol: u64,           // 8 - Payouts made so far
+    pub claimed_tokens: u64,        // 8 - Tokens burned for payouts so far
+    pub claim_deadline: i64,        // 8 - Claims close at this time (0 = never)
+    pub swept: bool,                // 1 - Unclaimed remainder has been swept
+    pub cranked_count: u32,         // 4 - Holders paid by crank_payouts
+    pub cranked_sol: u64,           // 8 - Payouts pushed by crank_payouts
+    pub rent_payer: Pubkey,         // 32 - Receives rent when closed
+}
+
+impl PayoutPool {
+    pub fn claim_window_closed(&self, now: i64) -> bool {
+        self.claim_deadline > 0 && now > self.claim_deadline
+    }
+
+    /// payout = (tokens / unclaimed_tokens) * unclaimed_sol
+    /// Same result as the full-pool ratio, but order-independent and it can
+    /// never pay out more than total_sol.
+    pub fn payout_for(&self, tokens: u64) -> Result<u64> {
+        let unclaimed_tokens = self
+            .remaining_supply
+            .checked_sub(self.claimed_tokens)
+            .ok_or(BoomError::Overflow)?;
+        require!(tokens <= unclaimed_tokens, BoomError::PayoutSupplyExceeded);
+        let unclaimed_sol = self
+            .total_sol
+            .checked_sub(self.claimed_sol)
+            .ok_or(BoomError::Overflow)?;
+        let payout = (tokens as u128)
+            .checked_mul(unclaimed_sol as u128)
+            .ok_or(BoomError::Overflow)?
+            .checked_div(unclaimed_tokens as u128)
+            .ok_or(BoomError::Overflow)?;
+        Ok(payout as u64)
+    }
+
+    pub fn record_claim(&mut self, payout: u64, tokens: u64) -> Result<()> {
+        self.claimed_count += 1;
+        self.claimed_sol = self.claimed_sol.checked_add(payout).ok_or(BoomError::Overflow)?;
+        self.claimed_tokens = self.claimed_tokens.checked_add(tokens).ok_or(BoomError::Overflow)?;
+        Ok(())
+    }
 }
 
 /// Custom AMM Pool for trading after presale
@@ -2551,6 +8830,158 @@ pub struct Pool {
     pub bump: u8,                   // 1
     pub token_vault_bump: u8,       // 1
     pub sol_vault_bump: u8,         // 1
+    pub mode: PoolMode,             // 1 - Bonding curve until graduation
+    pub curve: BondingCurveConfig,  // 35 - Curve parameters (bonding mode only)
+    pub curve_supply: u64,          // 8 - Tokens minted by the curve
+    pub launch_protection: LaunchProtectionConfig, // 24 - Anti-sniper window
+    pub fee_schedule: FeeScheduleConfig, // 16 - Deadline/volatility fee schedule
+    pub last_price: u64,            // 8 - Lamports per whole token after last trade
+    pub recent_move_bps: u16,       // 2 - Decaying recent price move
+    pub quote_mint: Pubkey,         // 32 - Quote asset mint (default = native SOL)
+    pub quote_authority_bump: u8,   // 1 - Bump of the quote vault authority PDA
+    pub rent_payer: Pubkey,         // 32 - Receives pool and vault rent when closed
+    pub ath_price: u64,             // 8 - Highest time-weighted price seen
+    pub holder_count: u32,          // 4 - Distinct buying wallets
+    pub quote_fees: u64,            // 8 - Fees collected in SOL/quote (sell-side AMM fees stay in tokens)
+    pub fees_carried_over: bool,    // 1 - carry_over_fees already ran
+    pub twap_price: u64,            // 8 - Time-weighted last_price, see Pool::time_weighted_price
+    pub twap_updated_at: i64,       // 8 - When twap_price was last folded
+    pub curve_seed: u64,            // 8 - SOL the bonding curve started with (presale + carry-over)
+}
+
+impl Pool {
+    /// recent_move_bps decayed by the time since the last trade (twap_updated_at)
+    fn decayed_move_bps(&self, now: i64) -> u16 {
+        let halvings = now.saturating_sub(self.twap_updated_at).max(0) / PRICE_MOVE_HALF_LIFE;
+        if halvings >= 16 {
+            0
+        } else {
+            self.recent_move_bps >> halvings
+        }
+    }
+
+    /// Bonding curve graduates once the curve itself has raised the threshold
+    /// The presale seed already sits in sol_reserve and does not count.
+    fn curve_graduates(&self) -> bool {
+        self.sol_reserve.saturating_sub(self.curve_seed) >= self.curve.graduation_threshold
+    }
+
+    /// Time-weighted price as of `now`: an average that moves toward last_price in
+    /// proportion to how long it has been held, reaching it after PRICE_TWAP_WINDOW.
+    /// A price pushed and reverted inside one transaction carries no weight.
+    fn time_weighted_price(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.twap_updated_at).clamp(0, PRICE_TWAP_WINDOW);
+        let twap = self.twap_price as i128;
+        let step = (self.last_price as i128 - twap) * elapsed as i128 / PRICE_TWAP_WINDOW as i128;
+        (twap + step) as u64
+    }
+}
+
+/// Vesting schedule of one winner's token allocation
+#[account]
+#[derive(InitSpace)]
+pub struct WinnerVesting {
+    pub round_id: u64,              // 8
+    pub winner: Pubkey,             // 32
+    pub total_amount: u64,          // 8 - Tokens allocated to the winner
+    pub claimed_amount: u64,        // 8 - Tokens minted so far
+    pub start_time: i64,            // 8 - Vesting start (trading start)
+    pub bump: u8,                   // 1
+}
+
+/// Per-wallet trading state used for launch protection
+#[account]
+#[derive(InitSpace)]
+pub struct TraderState {
+    pub round_id: u64,              // 8
+    pub user: Pubkey,               // 32
+    pub protected_buy_total: u64,   // 8 - SOL bought inside the protection window
+    pub last_trade_slot: u64,       // 8 - slot of the last swap
+    pub bump: u8,                   // 1
+    pub counted_holder: bool,       // 1 - Already counted in Pool.holder_count
+}
+
+/// Last-buyer jackpot - ring buffer of the most recent qualifying buyers
+#[account]
+#[derive(InitSpace)]
+pub struct Jackpot {
+    pub round_id: u64,              // 8
+    pub config: JackpotConfig,      // 11
+    pub buyers: [Pubkey; MAX_JACKPOT_BUYERS], // 320 - Ring buffer of recent buyers
+    pub next_index: u8,             // 1 - Next ring buffer slot to overwrite
+    pub buyer_count: u8,            // 1 - Filled slots (<= winner_count)
+    pub settled: bool,              // 1 - Funded at unwind_lp
+    pub total_amount: u64,          // 8 - SOL (or quote) set aside for winners
+    pub amount_per_winner: u64,     // 8
+    pub claimed_mask: u16,          // 2 - Bit per claimed slot
+    pub quote_mint: Pubkey,         // 32 - Quote asset mint (default = native SOL)
+    pub quote_authority_bump: u8,   // 1
+    pub bump: u8,                   // 1
+    pub rent_payer: Pubkey,         // 32 - Receives rent when closed
+}
+
+impl Jackpot {
+    fn record_buyer(&mut self, buyer: Pubkey) {
+        self.buyers[self.next_index as usize] = buyer;
+        self.next_index = (self.next_index + 1) % self.config.winner_count;
+        self.buyer_count = (self.buyer_count + 1).min(self.config.winner_count);
+    }
+
+    /// Split share_bps of the unwound SOL between the recorded buyers
+    fn settle(&mut self, total_sol: u64, quote_mint: Pubkey, quote_authority_bump: u8) -> Result<u64> {
+        self.settled = true;
+        self.quote_mint = quote_mint;
+        self.quote_authority_bump = quote_authority_bump;
+        if self.buyer_count == 0 {
+            return Ok(0);
+        }
+
+        let share = (total_sol as u128)
+            .checked_mul(self.config.share_bps as u128)
+            .ok_or(BoomError::Overflow)?
+            / 10000;
+        self.amount_per_winner = (share / self.buyer_count as u128) as u64;
+        self.total_amount = self
+            .amount_per_winner
+            .checked_mul(self.buyer_count as u64)
+            .ok_or(BoomError::Overflow)?;
+        Ok(self.total_amount)
+    }
+
+    fn all_claimed(&self) -> bool {
+        self.claimed_mask.count_ones() >= self.buyer_count as u32
+    }
+
+    /// Shares funded at unwind_lp that no winner has claimed yet
+    fn unclaimed_amount(&self) -> Result<u64> {
+        let unclaimed = (self.buyer_count as u32).saturating_sub(self.claimed_mask.count_ones());
+        Ok(self
+            .amount_per_winner
+            .checked_mul(unclaimed as u64)
+            .ok_or(BoomError::Overflow)?)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum PoolMode {
+    #[default]
+    ConstantProduct, // Standard x*y=k AMM
+    BondingCurve,    // Mint on buy / burn on sell until graduation
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum CurveKind {
+    #[default]
+    Linear,      // Price grows linearly with supply
+    Exponential, // Price grows by growth_bps every step_size tokens
+}
+
+/// Marker left at a closed round's id so the id cannot be started again
+#[account]
+#[derive(InitSpace)]
+pub struct RoundTombstone {
+    pub round_id: u64,              // 8
+    pub bump: u8,                   // 1
 }
 
 /// Manages automatic round progression
@@ -2567,6 +8998,66 @@ pub struct RoundSequencer {
     pub default_min_deposit: u64,       // 8
     pub default_max_deposit: u64,       // 8
     pub bump: u8,                       // 1
+    // Carry-over seeding of the next round's pool
+    pub fee_carry_over_bps: u16,        // 2 - Share of a round's SOL fees moved to the next round
+    pub unclaimed_carry_over_bps: u16,  // 2 - Share of NextRound sweeps kept for the next round
+    pub total_carried_over: u64,        // 8 - Lifetime carry-over across all rounds
+    pub default_timer_duration: i64,    // 8 - Timer advance_round starts on a live pool (0 = manual)
+}
+
+/// Size of a round sequencer created before the carry-over fields (see migrate_round_sequencer)
+const ROUND_SEQUENCER_V1_SPACE: usize = 8 + 32 + 8 + 8 + 1 + 8 + 4 + 8 + 8 + 1;
+
+impl RoundSequencer {
+    /// Count carry-over toward the lifetime total
+    fn record_carry_over(&mut self, amount: u64) -> Result<()> {
+        self.total_carried_over = self
+            .total_carried_over
+            .checked_add(amount)
+            .ok_or(BoomError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Liquidity carried into a round from the previous one - one record per receiving round
+#[account]
+#[derive(InitSpace)]
+pub struct RoundSeed {
+    pub round_id: u64,              // 8 - Round receiving the seed
+    pub from_fees: u64,             // 8 - Lamports from the previous round's fees
+    pub from_unclaimed: u64,        // 8 - Lamports from the previous round's unclaimed payouts
+    pub bump: u8,                   // 1
+    pub rent_payer: Pubkey,         // 32 - Receives rent when closed
+}
+
+impl RoundSeed {
+    /// Add carry-over, filling in the record the first time it is used
+    fn record(
+        &mut self,
+        round_id: u64,
+        bump: u8,
+        payer: Pubkey,
+        from_fees: u64,
+        from_unclaimed: u64,
+    ) -> Result<()> {
+        if self.rent_payer == Pubkey::default() {
+            self.round_id = round_id;
+            self.bump = bump;
+            self.rent_payer = payer;
+        }
+        self.from_fees = self.from_fees.checked_add(from_fees).ok_or(BoomError::Overflow)?;
+        self.from_unclaimed = self
+            .from_unclaimed
+            .checked_add(from_unclaimed)
+            .ok_or(BoomError::Overflow)?;
+        Ok(())
+    }
+
+    fn total(&self) -> Result<u64> {
+        self.from_fees
+            .checked_add(self.from_unclaimed)
+            .ok_or(error!(BoomError::Overflow))
+    }
 }
 
 // ==================== CONFIG ====================
@@ -2578,61 +9069,373 @@ pub struct ProtocolConfig {
     pub fee_bps: u16,
 }
 
-// ==================== EVENTS ====================
+/// Bonding curve parameters for a round
+/// Prices are in lamports per whole token (10^9 base units)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct BondingCurveConfig {
+    pub kind: CurveKind,
+    pub base_price: u64,            // Price at zero supply
+    pub slope: u64,                 // Linear: price increase per 10^9 whole tokens minted
+    pub growth_bps: u16,            // Exponential: price growth per step
+    pub step_size: u64,             // Exponential: base units per step
+    pub graduation_threshold: u64,  // SOL raised on the curve at which the pool graduates to the AMM
+}
+
+/// Launch protection window applied after the explosion timer starts
+/// A zero duration disables protection.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct LaunchProtectionConfig {
+    pub duration_seconds: i64,        // Window length after timer start
+    pub max_buy_per_wallet: u64,      // Max SOL each wallet can spend in the window (0 = no limit)
+    pub min_slots_between_trades: u64, // Cooldown between a wallet's trades (0 = none)
+}
+
+/// Dynamic fee schedule on top of the pool's base fee_bps
+/// All fees are in basis points; zero values disable each component.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct FeeScheduleConfig {
+    pub final_window_seconds: i64,      // Window before explosion_deadline with raised fees
+    pub final_window_buy_fee_bps: u16,  // Buy fee inside the final window
+    pub final_window_sell_fee_bps: u16, // Sell fee inside the final window
+    pub volatility_multiplier_bps: u16, // Surcharge = recent_move_bps * multiplier / 10000
+    pub max_fee_bps: u16,               // Cap on the effective fee (0 = 10%)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum AllocationMode {
+    #[default]
+    FixedPerWinner, // Lottery winners get tokens_per_winner each
+    ProRata,        // All depositors filled pro-rata up to raise_target
+    CappedProRata,  // Pro-rata with each wallet counted up to allocation_cap
+}
+
+/// Presale allocation settings chosen at start_presale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AllocationConfig {
+    pub mode: AllocationMode,
+    pub raise_target: u64,          // Pro-rata: max SOL filled across the round
+    pub allocation_cap: u64,        // Capped pro-rata: max SOL filled per wallet
+}
+
+/// Winner token vesting - cliff plus linear unlock from the trading start
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct VestingConfig {
+    pub cliff_seconds: i64,         // Nothing unlocks before the cliff
+    pub vesting_seconds: i64,       // Full unlock after this long (0 = no vesting)
+    pub unvested_counts_toward_supply: bool, // Unvested tokens share in explosion payouts
+}
+
+impl VestingConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.vesting_seconds > 0
+    }
+
+    /// Tokens unlocked out of `total` at `now` for a schedule starting at `start`
+    pub fn unlocked_amount(&self, total: u64, start: i64, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(start);
+        if !self.is_enabled() || elapsed >= self.vesting_seconds {
+            return Ok(total);
+        }
+        if elapsed < self.cliff_seconds {
+            return Ok(0);
+        }
+        let unlocked = (total as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(BoomError::Overflow)?
+            / self.vesting_seconds as u128;
+        Ok(unlocked as u64)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LaunchKind {
+    Presale,
+    Legacy,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LaunchStatus {
+    Presale,
+    Trading,
+    Exploded,
+}
+
+/// Where a presale round is in its pipeline - each step checks and advances it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RoundStage {
+    Presale,      // Deposits open
+    Lottery,      // Deposits closed, winners being drawn
+    TokenCreated, // Presale token created or registered
+    PoolLive,     // Trading pool created or LP registered
+    Exploded,     // Explosion triggered
+    Settled,      // LP unwound, payouts open
+}
+
+/// Where sweep_unclaimed_payout sends an expired payout remainder
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SweepDestination {
+    Treasury,
+    NextRound,
+}
+
+/// Presale end-time extension on late deposits (zero window = disabled)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AntiSnipeConfig {
+    pub window_seconds: i64,        // Deposits in the final N seconds extend the round
+    pub extension_seconds: i64,     // Extension per late deposit
+    pub max_total_extension: i64,   // Cap on the total extension
+}
+
+/// Extra per-round explosion triggers (zero values disable each one)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct ExplosionTriggerConfig {
+    pub volume_threshold: u128,     // Pool.total_volume that explodes the round (wash-tradable)
+    pub holder_threshold: u32,      // Distinct buying wallets that explode the round
+    pub drawdown_bps: u16,          // Drop from the all-time-high price that explodes the round
+    pub slot_probability_ppm: u32,  // Chance per committed roll (at most one per slot), in parts per million
+}
+
+/// Heat mode: buys of at least min_buy push the explosion deadline back
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct HeatModeConfig {
+    pub min_buy: u64,               // Smallest buy (quote units) that extends the timer
+    pub extension_seconds: i64,     // Extension per qualifying buy (0 = disabled)
+    pub max_total_extension: i64,   // Hard cap on the total extension
+}
+
+/// Last-buyer jackpot parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct JackpotConfig {
+    pub share_bps: u16,             // Share of the unwound SOL paid to the last buyers
+    pub winner_count: u8,           // Number of last buyers who split it (<= MAX_JACKPOT_BUYERS)
+    pub min_buy: u64,               // Smallest buy (quote units) that enters the ring buffer
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct PresaleStarted {
+    pub round_id: u64,
+    pub end_time: i64,
+    pub lottery_spots: u32,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+    pub allocation_mode: AllocationMode,
+    pub quote_mint: Pubkey,
+}
+
+#[event]
+pub struct DepositMade {
+    pub round_id: u64,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct WhitelistRootUpdated {
+    pub round_id: u64,
+    pub whitelist_root: [u8; 32],
+}
+
+#[event]
+pub struct PresaleFinalized {
+    pub round_id: u64,
+    pub total_deposited: u64,
+    pub total_depositors: u32,
+    pub winners_count: u32,
+}
+
+#[event]
+pub struct WinnerMarked {
+    pub round_id: u64,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub round_id: u64,
+    pub depositor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PresaleExtended {
+    pub round_id: u64,
+    pub new_end_time: i64,
+    pub total_extension: i64,
+}
+
+#[event]
+pub struct RaiseCapsSet {
+    pub round_id: u64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+}
+
+#[event]
+pub struct AntiSnipeSet {
+    pub round_id: u64,
+    pub window_seconds: i64,
+    pub extension_seconds: i64,
+    pub max_total_extension: i64,
+}
+
+#[event]
+pub struct SoftCapMissed {
+    pub round_id: u64,
+    pub total_deposited: u64,
+    pub soft_cap: u64,
+}
+
+#[event]
+pub struct LaunchFailed {
+    pub round_id: u64,
+    pub end_time: i64,
+    pub grace_period: i64,
+}
+
+#[event]
+pub struct DepositWithdrawn {
+    pub round_id: u64,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct WinnerClaimed {
+    pub round_id: u64,
+    pub winner: Pubkey,
+    pub deposit_amount: u64,
+    pub tokens_minted: u64,
+    pub purchase_cost: u64,
+    pub refund_amount: u64,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub round_id: u64,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub claimed_total: u64,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct PresaleTokenCreated {
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub total_supply: u64,
+    pub tokens_per_winner: u64,
+    pub launch_price: u64,
+}
+
+#[event]
+pub struct CapRangeCommitted {
+    pub round_id: u64,
+    pub cap_min: u64,
+    pub cap_max: u64,
+}
+
+#[event]
+pub struct CapPuzzleCommitted {
+    pub round_id: u64,
+    pub seed: [u8; 32],
+    pub iterations: u64,
+    pub ciphertext: u64,
+    pub key_hash: [u8; 32],
+}
+
+#[event]
+pub struct CapRandomnessCommitted {
+    pub round_id: u64,
+    pub randomness_account: Pubkey,
+}
+
+#[event]
+pub struct ExplosionTriggersSet {
+    pub round_id: u64,
+    pub triggers: ExplosionTriggerConfig,
+}
+
+#[event]
+pub struct RandomRollCommitted {
+    pub round_id: u64,
+    pub randomness_account: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct HeatModeSet {
+    pub round_id: u64,
+    pub heat: HeatModeConfig,
+}
 
 #[event]
-pub struct PresaleStarted {
+pub struct TimerExtended {
     pub round_id: u64,
-    pub end_time: i64,
-    pub lottery_spots: u32,
-    pub min_deposit: u64,
-    pub max_deposit: u64,
+    pub new_deadline: i64,
+    pub total_extension: i64,
 }
 
 #[event]
-pub struct DepositMade {
+pub struct JackpotConfigured {
     pub round_id: u64,
-    pub depositor: Pubkey,
-    pub amount: u64,
-    pub total_amount: u64,
+    pub config: JackpotConfig,
 }
 
 #[event]
-pub struct PresaleFinalized {
+pub struct JackpotFunded {
     pub round_id: u64,
-    pub total_deposited: u64,
-    pub total_depositors: u32,
-    pub winners_count: u32,
+    pub total_amount: u64,
+    pub amount_per_winner: u64,
+    pub winners: u8,
 }
 
 #[event]
-pub struct WinnerMarked {
+pub struct JackpotClaimed {
     pub round_id: u64,
     pub winner: Pubkey,
+    pub index: u8,
     pub amount: u64,
 }
 
 #[event]
-pub struct RefundClaimed {
-    pub round_id: u64,
-    pub depositor: Pubkey,
-    pub amount: u64,
+pub struct CarryOverSet {
+    pub fee_carry_over_bps: u16,
+    pub unclaimed_carry_over_bps: u16,
 }
 
 #[event]
-pub struct WinnerClaimed {
+pub struct RoundAccountMigrated {
     pub round_id: u64,
-    pub winner: Pubkey,
-    pub deposit_amount: u64,
-    pub tokens_minted: u64,
+    pub account: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
 }
 
 #[event]
-pub struct PresaleTokenCreated {
-    pub round_id: u64,
+pub struct RoundTimerSet {
+    pub default_timer_duration: i64,
+}
+
+#[event]
+pub struct RoundSeedCarriedOver {
+    pub from_round: u64,
+    pub to_round: u64,
+    pub from_fees: u64,
+    pub from_unclaimed: u64,
+    pub total_seed: u64,            // Seed collected so far for to_round
+}
+
+#[event]
+pub struct LaunchCreated {
     pub mint: Pubkey,
-    pub total_supply: u64,
-    pub tokens_per_winner: u64,
+    pub kind: LaunchKind,
+    pub round_id: u64,
 }
 
 #[event]
@@ -2691,6 +9494,23 @@ pub struct PayoutClaimed {
     pub amount: u64,
 }
 
+#[event]
+pub struct PayoutsCranked {
+    pub round_id: u64,
+    pub processed: u32,
+    pub total_paid: u64,
+    pub claimed_count: u32,
+    pub claimed_sol: u64,
+}
+
+#[event]
+pub struct UnclaimedPayoutSwept {
+    pub round_id: u64,
+    pub amount: u64,
+    pub destination: SweepDestination,
+    pub destination_account: Pubkey,
+}
+
 #[event]
 pub struct PythPriceUsed {
     pub sol_usd_price: u64,
@@ -2704,11 +9524,22 @@ pub struct LpUnwound {
     pub remaining_supply: u64,
 }
 
+#[event]
+pub struct QuoteVaultsInitialized {
+    pub round_id: u64,
+    pub quote_mint: Pubkey,
+    pub presale_vault: Pubkey,
+    pub pool_vault: Pubkey,
+    pub payout_vault: Pubkey,
+}
+
 #[event]
 pub struct NextRoundStarted {
     pub previous_round: u64,
     pub new_round: u64,
     pub auto_advanced: bool,
+    pub previous_failed: bool,
+    pub carried_over_seed: u64,     // Lamports already waiting in the new round's pool vault
 }
 
 #[event]
@@ -2717,6 +9548,12 @@ pub struct RoundSequencerUpdated {
     pub last_explosion_round: u64,
 }
 
+#[event]
+pub struct RoundStageChanged {
+    pub round_id: u64,
+    pub stage: RoundStage,
+}
+
 // ==================== CUSTOM AMM EVENTS ====================
 
 #[event]
@@ -2736,6 +9573,7 @@ pub struct SwapExecuted {
     pub amount_in: u64,
     pub amount_out: u64,
     pub fee_amount: u64,
+    pub fee_bps: u16,
     pub new_sol_reserve: u64,
     pub new_token_reserve: u64,
 }
@@ -2756,6 +9594,24 @@ pub struct PoolTokensDeposited {
     pub amount: u64,
 }
 
+#[event]
+pub struct BondingPoolCreated {
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub kind: CurveKind,
+    pub base_price: u64,
+    pub graduation_threshold: u64,
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct PoolGraduated {
+    pub round_id: u64,
+    pub curve_supply: u64,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+}
+
 // ==================== ERRORS ====================
 
 #[error_code]
@@ -2845,4 +9701,1000 @@ pub enum BoomError {
     InsufficientDeposit,
     #[msg("Insufficient liquidity in pool")]
     InsufficientLiquidity,
+    // Bonding curve errors
+    #[msg("Invalid bonding curve configuration")]
+    InvalidCurveConfig,
+    #[msg("Pool is still on its bonding curve")]
+    CurveNotGraduated,
+    #[msg("Pool has graduated from its bonding curve")]
+    CurveGraduated,
+    // Launch protection errors
+    #[msg("Wallet buy limit exceeded during launch protection")]
+    WalletBuyLimitExceeded,
+    #[msg("Trade cooldown active - wait more slots")]
+    TradeCooldownActive,
+    // Allocation errors
+    #[msg("Invalid presale allocation configuration")]
+    InvalidAllocationConfig,
+    #[msg("Lottery winners are not used in pro-rata rounds")]
+    LotteryNotUsed,
+    #[msg("Round uses native SOL as its quote asset")]
+    NativeQuoteRound,
+    #[msg("Quote token accounts are required for this round")]
+    MissingQuoteAccounts,
+    #[msg("Quote mint not supported for this operation")]
+    UnsupportedQuoteMint,
+    #[msg("Quote token account has the wrong mint or owner")]
+    InvalidQuoteAccount,
+    #[msg("Wallet is not on the presale whitelist")]
+    NotWhitelisted,
+    #[msg("Early-withdraw penalty too high")]
+    InvalidWithdrawPenalty,
+    #[msg("Round already has deposits")]
+    DepositsAlreadyMade,
+    #[msg("Withdrawal exceeds deposit")]
+    WithdrawExceedsDeposit,
+    #[msg("Launch has not failed - trading started or grace period not over")]
+    LaunchNotFailed,
+    #[msg("Launch failed - grace period for starting trading is over")]
+    LaunchFailed,
+    #[msg("Not enough funds left in the round for this refund")]
+    InsufficientRefundFunds,
+    #[msg("Soft cap cannot exceed hard cap")]
+    InvalidRaiseCaps,
+    #[msg("Presale hard cap reached")]
+    HardCapReached,
+    #[msg("Invalid vesting configuration")]
+    InvalidVestingConfig,
+    #[msg("Vesting account required for this round")]
+    MissingVestingAccount,
+    #[msg("No vested tokens to claim")]
+    NothingVested,
+    #[msg("Unvested tokens were forfeited at explosion")]
+    VestingForfeited,
+    #[msg("Token balance exceeds the unclaimed payout supply")]
+    PayoutSupplyExceeded,
+    #[msg("Payout claim window has closed")]
+    ClaimWindowClosed,
+    #[msg("Payout claim window is still open")]
+    ClaimWindowOpen,
+    #[msg("Unclaimed payout already swept")]
+    AlreadySwept,
+    #[msg("Sweep destination account missing")]
+    MissingSweepDestination,
+    #[msg("Invalid sweep destination")]
+    InvalidSweepDestination,
+    #[msg("Crank expects [holder_token_account, recipient] pairs")]
+    InvalidCrankAccounts,
+    #[msg("Mint has no PermanentDelegate set to the payout delegate")]
+    MissingPermanentDelegate,
+    #[msg("Account still has unsettled claims or dependents")]
+    AccountNotSettled,
+    #[msg("Launch record does not match this round")]
+    InvalidLaunch,
+    #[msg("Invalid cap bounds")]
+    InvalidCapBounds,
+    #[msg("Cap is outside the protocol min/max bounds")]
+    CapOutOfBounds,
+    #[msg("Cap is outside the disclosed range")]
+    CapOutOfCommittedRange,
+    #[msg("Cap range already committed")]
+    CapRangeAlreadyCommitted,
+    #[msg("Explosion timer already started")]
+    TimerAlreadyStarted,
+    #[msg("Cap puzzle already committed")]
+    CapPuzzleAlreadyCommitted,
+    #[msg("Invalid cap puzzle")]
+    InvalidCapPuzzle,
+    #[msg("No cap puzzle committed for this round")]
+    NoCapPuzzle,
+    #[msg("Randomness provider is not supported")]
+    UnsupportedRandomnessProvider,
+    #[msg("Randomness account does not match the committed one")]
+    InvalidRandomnessAccount,
+    #[msg("Randomness already revealed")]
+    RandomnessAlreadyRevealed,
+    #[msg("Randomness not revealed yet")]
+    RandomnessNotRevealed,
+    #[msg("Revealed randomness does not match the commitment")]
+    InvalidRandomnessReveal,
+    #[msg("Market cap has not reached the cap")]
+    CapNotReached,
+    #[msg("Invalid explosion trigger config")]
+    InvalidExplosionTriggers,
+    #[msg("Explosion trigger not configured for this round")]
+    TriggerNotConfigured,
+    #[msg("Explosion trigger threshold not reached")]
+    TriggerNotReached,
+    #[msg("Random trigger already rolled this slot")]
+    SlotAlreadyRolled,
+    #[msg("Invalid SlotHashes sysvar data")]
+    InvalidSlotHashes,
+    #[msg("Invalid heat mode config")]
+    InvalidHeatMode,
+    #[msg("Invalid jackpot config")]
+    InvalidJackpotConfig,
+    #[msg("Round has a jackpot - jackpot account required")]
+    MissingJackpotAccount,
+    #[msg("Signer is not the jackpot winner at this index")]
+    NotJackpotWinner,
+    #[msg("Jackpot share already claimed")]
+    JackpotAlreadyClaimed,
+    #[msg("Invalid carry-over config")]
+    InvalidCarryOver,
+    #[msg("Fees already carried over for this round")]
+    FeesAlreadyCarriedOver,
+    #[msg("Trader state required while launch protection or the holder trigger is active")]
+    MissingTraderState,
+    #[msg("Deposit does not cover the winner purchase cost")]
+    DepositBelowPurchaseCost,
+    #[msg("Quote mints with a transfer fee are not supported")]
+    TransferFeeQuoteMint,
+    #[msg("Instruction not allowed at the round's current stage")]
+    InvalidRoundStage,
+    #[msg("Round uses a lottery - the authority must end the presale")]
+    LotteryDrawRequired,
+    #[msg("A random roll is already pending")]
+    RandomRollPending,
+    #[msg("No random roll has been committed")]
+    NoRandomRoll,
+    #[msg("Round sequencer already uses the current layout")]
+    SequencerUpToDate,
+    #[msg("Invalid round sequencer account")]
+    InvalidSequencer,
+    #[msg("Round sequencer has no default timer - start the timer manually")]
+    RoundTimerNotSet,
+    #[msg("Explosion account required to start the timer")]
+    MissingExplosionAccount,
+    #[msg("Claim window is shorter than the protocol minimum")]
+    ClaimWindowTooShort,
+    #[msg("Round account already uses the current layout")]
+    RoundAccountUpToDate,
+    #[msg("Invalid round account for migration")]
+    InvalidRoundAccount,
+    #[msg("Fee cap must not be below the base fee")]
+    InvalidFeeSchedule,
+    #[msg("Round id belongs to a closed round")]
+    RoundIdRetired,
+}
+
+// ==================== TESTS ====================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIT: u64 = CURVE_TOKEN_UNIT as u64;
+
+    fn linear_curve(base_price: u64, slope: u64) -> BondingCurveConfig {
+        BondingCurveConfig {
+            kind: CurveKind::Linear,
+            base_price,
+            slope,
+            ..BondingCurveConfig::default()
+        }
+    }
+
+    fn exponential_curve(base_price: u64, growth_bps: u16, step_size: u64) -> BondingCurveConfig {
+        BondingCurveConfig {
+            kind: CurveKind::Exponential,
+            base_price,
+            growth_bps,
+            step_size,
+            ..BondingCurveConfig::default()
+        }
+    }
+
+    // ---------- bonding curve ----------
+
+    #[test]
+    fn linear_spot_price_grows_by_slope_per_billion_tokens() {
+        let curve = linear_curve(1_000, 2_000);
+        assert_eq!(curve_spot_price(&curve, 0).unwrap(), 1_000);
+        assert_eq!(curve_spot_price(&curve, 1_000_000_000 * UNIT).unwrap(), 3_000);
+    }
+
+    #[test]
+    fn linear_cumulative_cost_is_area_under_price() {
+        // 1e9 tokens from price 1000 to 3000 cost the average price 2000 each
+        let curve = linear_curve(1_000, 2_000);
+        assert_eq!(curve_cumulative_cost(&curve, 0).unwrap(), 0);
+        assert_eq!(
+            curve_cumulative_cost(&curve, 1_000_000_000 * UNIT).unwrap(),
+            2_000_000_000_000
+        );
+        // Flat curve: every whole token costs base_price
+        let flat = linear_curve(1_000, 0);
+        assert_eq!(curve_cumulative_cost(&flat, 5 * UNIT).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn growth_factor_compounds_per_step() {
+        assert_eq!(curve_growth_factor(0, 50).unwrap(), CURVE_PRECISION);
+        assert_eq!(curve_growth_factor(10_000, 3).unwrap(), 8 * CURVE_PRECISION);
+        assert_eq!(
+            curve_growth_factor(1_000, 2).unwrap(),
+            CURVE_PRECISION * 121 / 100
+        );
+    }
+
+    #[test]
+    fn exponential_price_is_constant_within_a_step() {
+        let curve = exponential_curve(1_000, 1_000, UNIT);
+        assert_eq!(curve_spot_price(&curve, 0).unwrap(), 1_000);
+        assert_eq!(curve_spot_price(&curve, UNIT - 1).unwrap(), 1_000);
+        assert_eq!(curve_spot_price(&curve, 2 * UNIT).unwrap(), 1_210);
+    }
+
+    #[test]
+    fn exponential_cumulative_cost_sums_full_and_partial_steps() {
+        let curve = exponential_curve(1_000, 1_000, UNIT);
+        // 1000 + 1100 for two full steps, then half a step at 1210
+        assert_eq!(curve_cumulative_cost(&curve, 2 * UNIT).unwrap(), 2_100);
+        assert_eq!(curve_cumulative_cost(&curve, 2 * UNIT + UNIT / 2).unwrap(), 2_705);
+    }
+
+    #[test]
+    fn cumulative_cost_never_decreases() {
+        for curve in [linear_curve(1_000, 50_000), exponential_curve(1_000, 250, 10 * UNIT)] {
+            let mut previous = 0;
+            for tokens in [0, 1, UNIT, 7 * UNIT, 33 * UNIT + 17, 1_000 * UNIT] {
+                let cost = curve_cumulative_cost(&curve, tokens).unwrap();
+                assert!(cost >= previous);
+                previous = cost;
+            }
+        }
+    }
+
+    #[test]
+    fn curve_math_reports_overflow() {
+        let curve = exponential_curve(u64::MAX, 10_000, 1);
+        assert!(curve_spot_price(&curve, 200).is_err());
+    }
+
+    // ---------- winner purchase cost ----------
+
+    fn presale_token(tokens_per_winner: u64, launch_price: u64) -> PresaleToken {
+        PresaleToken {
+            round_id: 1,
+            mint: Pubkey::default(),
+            total_supply: 0,
+            tokens_per_winner,
+            bump: 0,
+            launch_price,
+            rent_payer: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn purchase_cost_is_tokens_times_launch_price() {
+        // 1,000 whole tokens at 0.001 SOL each
+        let token = presale_token(1_000 * UNIT, 1_000_000);
+        assert_eq!(token.winner_purchase_cost(5_000_000_000).unwrap(), 1_000_000_000);
+        assert_eq!(token.winner_purchase_cost(1_000_000_000).unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn purchase_cost_rejects_underfunded_winner() {
+        let token = presale_token(1_000 * UNIT, 1_000_000);
+        assert!(token.winner_purchase_cost(999_999_999).is_err());
+        assert!(token.winner_purchase_cost(0).is_err());
+    }
+
+    // ---------- presale rounds ----------
+
+    fn presale_round() -> PresaleRound {
+        PresaleRound {
+            authority: Pubkey::default(),
+            round_id: 1,
+            start_time: 0,
+            end_time: 1_000,
+            lottery_spots: 10,
+            min_deposit: 0,
+            max_deposit: 0,
+            total_deposited: 0,
+            total_depositors: 0,
+            is_finalized: false,
+            bump: 0,
+            allocation: AllocationConfig::default(),
+            total_capped_deposited: 0,
+            winners_committed: 0,
+            quote_mint: Pubkey::default(),
+            quote_authority_bump: 0,
+            whitelist_root: [0u8; 32],
+            withdraw_penalty_bps: 0,
+            penalties_collected: 0,
+            launch_grace_period: 100,
+            launch_failed: false,
+            soft_cap: 0,
+            hard_cap: 0,
+            anti_snipe: AntiSnipeConfig::default(),
+            total_extension: 0,
+            vesting: VestingConfig::default(),
+            vesting_allocated: 0,
+            vesting_minted: 0,
+            settled_deposits: 0,
+            rent_payer: Pubkey::default(),
+            stage: RoundStage::Presale,
+            claim_window: DEFAULT_CLAIM_WINDOW_SECONDS,
+            winner_count: 0,
+            allocation_claimed: 0,
+        }
+    }
+
+    #[test]
+    fn round_fails_when_trading_misses_the_grace_period() {
+        let mut round = presale_round();
+        round.stage = RoundStage::TokenCreated;
+        assert!(!round.is_failed(1_100));
+        assert!(round.is_failed(1_101));
+    }
+
+    #[test]
+    fn round_with_live_pool_never_fails() {
+        // Winners' SOL already sits in the pool vault - failed-launch refunds
+        // would pay early claimers out of losing depositors' funds
+        let mut round = presale_round();
+        for stage in [RoundStage::PoolLive, RoundStage::Exploded, RoundStage::Settled] {
+            round.stage = stage;
+            assert!(!round.is_failed(1_000_000));
+        }
+    }
+
+    #[test]
+    fn losers_refund_once_the_pool_is_live_without_a_timer() {
+        // Pool created but the explosion timer never started, long past the grace period
+        let mut round = presale_round();
+        round.stage = RoundStage::Lottery;
+        assert!(!round.refunds_open(1_050));
+
+        round.stage = RoundStage::PoolLive;
+        assert!(!round.is_failed(1_000_000));
+        assert!(round.refunds_open(1_000_000));
+    }
+
+    #[test]
+    fn failed_round_opens_refunds() {
+        let mut round = presale_round();
+        round.stage = RoundStage::TokenCreated;
+        assert!(!round.refunds_open(1_100));
+        assert!(round.refunds_open(1_101));
+    }
+
+    #[test]
+    fn round_missing_soft_cap_fails_at_end_time() {
+        let mut round = presale_round();
+        round.soft_cap = 500;
+        round.total_deposited = 499;
+        assert!(!round.is_failed(999));
+        assert!(round.is_failed(1_000));
+    }
+
+    #[test]
+    fn trader_state_closes_once_the_round_is_over() {
+        let mut round = presale_round();
+        round.stage = RoundStage::TokenCreated;
+        assert!(!round_over(false, Some(&round), 1_100));
+        assert!(round_over(false, Some(&round), 1_101));
+        assert!(round_over(false, None, 0));
+        round.stage = RoundStage::PoolLive;
+        assert!(!round_over(false, Some(&round), i64::MAX));
+        assert!(round_over(true, Some(&round), 0));
+    }
+
+    fn presale_explosion() -> PresaleExplosion {
+        let data = vec![0u8; 8 + PresaleExplosion::INIT_SPACE];
+        PresaleExplosion::try_deserialize_unchecked(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn stage_advances_only_from_listed_stages() {
+        let mut round = presale_round();
+        assert!(round
+            .advance_stage(&[RoundStage::Lottery], RoundStage::TokenCreated)
+            .is_err());
+        assert!(round.stage == RoundStage::Presale);
+
+        round
+            .advance_stage(&[RoundStage::Presale], RoundStage::Lottery)
+            .unwrap();
+        assert!(round.stage == RoundStage::Lottery);
+        round.require_stage(&[RoundStage::Lottery]).unwrap();
+        assert!(round.require_stage(&[RoundStage::Presale]).is_err());
+    }
+
+    #[test]
+    fn finalizing_opens_the_lottery_after_end_time() {
+        let mut round = presale_round();
+        assert!(finalize_presale(&mut round, 0, 999).is_err());
+
+        finalize_presale(&mut round, 0, 1_000).unwrap();
+        assert!(round.is_finalized && !round.launch_failed);
+        assert!(round.stage == RoundStage::Lottery);
+        assert!(finalize_presale(&mut round, 0, 1_000).is_err());
+    }
+
+    #[test]
+    fn finalizing_below_soft_cap_fails_the_round() {
+        let mut round = presale_round();
+        round.soft_cap = 500;
+        round.total_deposited = 499;
+        finalize_presale(&mut round, 0, 1_000).unwrap();
+        assert!(round.launch_failed);
+        assert!(round.stage == RoundStage::Lottery);
+    }
+
+    #[test]
+    fn timer_starts_only_once_the_pool_is_live() {
+        let mut round = presale_round();
+        let mut explosion = presale_explosion();
+        round.stage = RoundStage::TokenCreated;
+        assert!(start_timer(&mut explosion, &round, 60, 1_050).is_err());
+
+        round.stage = RoundStage::PoolLive;
+        assert!(start_timer(&mut explosion, &round, 0, 1_050).is_err());
+        start_timer(&mut explosion, &round, 60, 1_050).unwrap();
+        assert_eq!(explosion.explosion_deadline, 1_110);
+        assert_eq!(explosion.timer_started_at, 1_050);
+        assert!(start_timer(&mut explosion, &round, 60, 1_060).is_err());
+    }
+
+    // ---------- vesting ----------
+
+    #[test]
+    fn vesting_unlocks_nothing_before_cliff_then_linearly() {
+        let vesting = VestingConfig {
+            cliff_seconds: 100,
+            vesting_seconds: 1_000,
+            unvested_counts_toward_supply: false,
+        };
+        assert_eq!(vesting.unlocked_amount(1_000, 5_000, 5_099).unwrap(), 0);
+        assert_eq!(vesting.unlocked_amount(1_000, 5_000, 5_100).unwrap(), 100);
+        assert_eq!(vesting.unlocked_amount(1_000, 5_000, 5_500).unwrap(), 500);
+        assert_eq!(vesting.unlocked_amount(1_000, 5_000, 6_000).unwrap(), 1_000);
+        assert_eq!(vesting.unlocked_amount(1_000, 5_000, 9_999).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn vesting_disabled_unlocks_everything() {
+        let vesting = VestingConfig::default();
+        assert!(!vesting.is_enabled());
+        assert_eq!(vesting.unlocked_amount(1_000, 5_000, 0).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn vesting_before_start_unlocks_nothing() {
+        let vesting = VestingConfig {
+            cliff_seconds: 0,
+            vesting_seconds: 1_000,
+            unvested_counts_toward_supply: false,
+        };
+        assert_eq!(vesting.unlocked_amount(1_000, 5_000, 4_000).unwrap(), 0);
+        // Large allocations are computed in u128 without overflowing
+        let expected = (u64::MAX as u128 * 999 / 1_000) as u64;
+        assert_eq!(vesting.unlocked_amount(u64::MAX, 0, 999).unwrap(), expected);
+    }
+
+    // ---------- payouts ----------
+
+    fn payout_pool(total_sol: u64, remaining_supply: u64) -> PayoutPool {
+        PayoutPool {
+            round_id: 1,
+            total_sol,
+            remaining_supply,
+            claimed_count: 0,
+            bump: 0,
+            quote_mint: Pubkey::default(),
+            quote_authority_bump: 0,
+            mint: Pubkey::default(),
+            claimed_sol: 0,
+            claimed_tokens: 0,
+            claim_deadline: 0,
+            swept: false,
+            cranked_count: 0,
+            cranked_sol: 0,
+            rent_payer: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn payout_is_proportional_to_tokens() {
+        let pool = payout_pool(1_000, 400);
+        assert_eq!(pool.payout_for(100).unwrap(), 250);
+        assert_eq!(pool.payout_for(400).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn payouts_never_exceed_total_sol() {
+        // Rounding down on every claim leaves dust for the last holder, never a shortfall
+        let mut pool = payout_pool(1_000, 3);
+        for _ in 0..3 {
+            let payout = pool.payout_for(1).unwrap();
+            pool.record_claim(payout, 1).unwrap();
+        }
+        assert_eq!(pool.claimed_sol, 1_000);
+        assert_eq!(pool.claimed_tokens, 3);
+        assert_eq!(pool.claimed_count, 3);
+    }
+
+    #[test]
+    fn payout_rejects_more_tokens_than_unclaimed() {
+        let mut pool = payout_pool(1_000, 400);
+        let payout = pool.payout_for(300).unwrap();
+        pool.record_claim(payout, 300).unwrap();
+        assert!(pool.payout_for(101).is_err());
+        assert_eq!(pool.payout_for(100).unwrap(), 250);
+    }
+
+    #[test]
+    fn claim_window_closes_after_deadline() {
+        let mut pool = payout_pool(1_000, 400);
+        assert!(!pool.claim_window_closed(i64::MAX));
+        pool.claim_deadline = 500;
+        assert!(!pool.claim_window_closed(500));
+        assert!(pool.claim_window_closed(501));
+    }
+
+    #[test]
+    fn claim_window_has_a_protocol_minimum() {
+        assert!(validate_claim_window(0).is_ok());
+        assert!(validate_claim_window(1).is_err());
+        assert!(validate_claim_window(MIN_CLAIM_WINDOW_SECONDS - 1).is_err());
+        assert!(validate_claim_window(MIN_CLAIM_WINDOW_SECONDS).is_ok());
+        assert!(validate_claim_window(DEFAULT_CLAIM_WINDOW_SECONDS).is_ok());
+    }
+
+    #[test]
+    fn claim_deadline_follows_the_round_window() {
+        let mut round = presale_round();
+        assert_eq!(round.claim_deadline(100).unwrap(), 100 + DEFAULT_CLAIM_WINDOW_SECONDS);
+        round.claim_window = 0;
+        assert_eq!(round.claim_deadline(100).unwrap(), 0);
+    }
+
+    #[test]
+    fn unclaimed_winner_allocation_counts_toward_payout_supply() {
+        let mut round = presale_round();
+        round.winner_count = 3;
+        assert_eq!(round.unclaimed_allocation(100).unwrap(), 300);
+        round.allocation_claimed = 100;
+        assert_eq!(round.unclaimed_allocation(100).unwrap(), 200);
+
+        // Pro-rata: the round allocation is filled in proportion to raise_target
+        round.allocation.mode = AllocationMode::ProRata;
+        round.allocation.raise_target = 1_000;
+        round.lottery_spots = 10;
+        round.total_deposited = 500;
+        round.allocation_claimed = 0;
+        assert_eq!(round.winner_allocation(100).unwrap(), 500);
+        round.total_deposited = 4_000;
+        assert_eq!(round.winner_allocation(100).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn purchase_cost_without_launch_price_is_whole_deposit() {
+        let token = presale_token(1_000 * UNIT, 0);
+        assert_eq!(token.winner_purchase_cost(123).unwrap(), 123);
+    }
+
+    // ---------- jackpot ----------
+
+    fn jackpot(winner_count: u8) -> Jackpot {
+        Jackpot {
+            round_id: 1,
+            config: JackpotConfig {
+                share_bps: 1_000,
+                winner_count,
+                min_buy: 0,
+            },
+            buyers: [Pubkey::default(); MAX_JACKPOT_BUYERS],
+            next_index: 0,
+            buyer_count: 0,
+            settled: false,
+            total_amount: 0,
+            amount_per_winner: 0,
+            claimed_mask: 0,
+            quote_mint: Pubkey::default(),
+            quote_authority_bump: 0,
+            bump: 0,
+            rent_payer: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn jackpot_keeps_the_last_buyers() {
+        let buyers: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let mut jackpot = jackpot(3);
+        for buyer in &buyers {
+            jackpot.record_buyer(*buyer);
+        }
+
+        assert_eq!(jackpot.buyer_count, 3);
+        assert_eq!(jackpot.next_index, 2);
+        assert_eq!(&jackpot.buyers[..3], &[buyers[3], buyers[4], buyers[2]]);
+    }
+
+    #[test]
+    fn jackpot_splits_its_share_between_buyers() {
+        let mut jackpot = jackpot(3);
+        for _ in 0..3 {
+            jackpot.record_buyer(Pubkey::new_unique());
+        }
+
+        // 10% of 10_000 split three ways, the remainder stays in the vault
+        assert_eq!(jackpot.settle(10_000, Pubkey::default(), 0).unwrap(), 999);
+        assert!(jackpot.settled);
+        assert_eq!(jackpot.amount_per_winner, 333);
+
+        assert!(!jackpot.all_claimed());
+        jackpot.claimed_mask = 0b111;
+        assert!(jackpot.all_claimed());
+    }
+
+    #[test]
+    fn jackpot_reports_unclaimed_shares_for_the_sweep() {
+        let mut jackpot = jackpot(3);
+        for _ in 0..3 {
+            jackpot.record_buyer(Pubkey::new_unique());
+        }
+        assert_eq!(jackpot.unclaimed_amount().unwrap(), 0);
+
+        jackpot.settle(10_000, Pubkey::default(), 0).unwrap();
+        assert_eq!(jackpot.unclaimed_amount().unwrap(), 999);
+        jackpot.claimed_mask = 0b101;
+        assert_eq!(jackpot.unclaimed_amount().unwrap(), 333);
+        jackpot.claimed_mask = 0b111;
+        assert_eq!(jackpot.unclaimed_amount().unwrap(), 0);
+    }
+
+    #[test]
+    fn jackpot_without_buyers_pays_nothing() {
+        let mut jackpot = jackpot(3);
+        assert_eq!(jackpot.settle(10_000, Pubkey::default(), 0).unwrap(), 0);
+        assert!(jackpot.settled);
+        assert!(jackpot.all_claimed());
+    }
+
+    // ---------- carry-over ----------
+
+    #[test]
+    fn round_seed_accumulates_per_round() {
+        let payer = Pubkey::new_unique();
+        let mut seed = RoundSeed {
+            round_id: 0,
+            from_fees: 0,
+            from_unclaimed: 0,
+            bump: 0,
+            rent_payer: Pubkey::default(),
+        };
+        seed.record(4, 254, payer, 300, 0).unwrap();
+        seed.record(4, 254, Pubkey::new_unique(), 0, 200).unwrap();
+
+        assert_eq!((seed.round_id, seed.bump, seed.rent_payer), (4, 254, payer));
+        assert_eq!(seed.total().unwrap(), 500);
+        assert!(seed.record(4, 254, payer, u64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn sequencer_layout_only_appends_to_v1() {
+        assert_eq!(ROUND_SEQUENCER_V1_SPACE + 2 + 2 + 8 + 8, 8 + RoundSequencer::INIT_SPACE);
+    }
+
+    // ---------- account migration ----------
+
+    fn v1_tail<T: AccountSerialize>(account: &T, v1_space: usize) -> u8 {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data[v1_space - 1]
+    }
+
+    #[test]
+    fn round_accounts_only_append_to_v1() {
+        // The last first-deployment field must still end exactly at the V1 size
+        let mut round = presale_round();
+        round.bump = 0xAB;
+        assert_eq!(v1_tail(&round, PRESALE_ROUND_V1_SPACE), 0xAB);
+
+        let mut token = presale_token(1, 0);
+        token.bump = 0xAB;
+        assert_eq!(v1_tail(&token, PRESALE_TOKEN_V1_SPACE), 0xAB);
+
+        let mut explosion = presale_explosion();
+        explosion.bump = 0xAB;
+        assert_eq!(v1_tail(&explosion, PRESALE_EXPLOSION_V1_SPACE), 0xAB);
+
+        let mut payout = payout_pool(0, 0);
+        payout.bump = 0xAB;
+        assert_eq!(v1_tail(&payout, PAYOUT_POOL_V1_SPACE), 0xAB);
+
+        let mut pool = priced_pool(0, 0, 0);
+        pool.sol_vault_bump = 0xAB;
+        assert_eq!(v1_tail(&pool, POOL_V1_SPACE), 0xAB);
+
+        let deposit = UserDeposit {
+            depositor: Pubkey::default(),
+            round_id: 1,
+            amount: 0,
+            deposit_time: 0,
+            is_winner: false,
+            claimed: false,
+            bump: 0xAB,
+            refund_claimed: false,
+        };
+        assert_eq!(v1_tail(&deposit, USER_DEPOSIT_V1_SPACE), 0xAB);
+
+        let lp_info = LpInfo {
+            round_id: 1,
+            pool_id: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+            vault_a: Pubkey::default(),
+            vault_b: Pubkey::default(),
+            registered_at: 0,
+            bump: 0xAB,
+            rent_payer: Pubkey::default(),
+        };
+        assert_eq!(v1_tail(&lp_info, LP_INFO_V1_SPACE), 0xAB);
+
+        let mut explosion = presale_explosion();
+        explosion.is_exploded = true;
+        let mut data = Vec::new();
+        explosion.try_serialize(&mut data).unwrap();
+        assert_eq!(data[PRESALE_EXPLOSION_V1_EXPLODED_OFFSET], 1);
+    }
+
+    #[test]
+    fn migration_recognises_round_accounts() {
+        assert_eq!(
+            RoundAccountKind::from_discriminator(&PayoutPool::DISCRIMINATOR).unwrap(),
+            RoundAccountKind::PayoutPool
+        );
+        assert!(RoundAccountKind::from_discriminator(&RoundSequencer::DISCRIMINATOR).is_err());
+        for kind in [RoundAccountKind::PresaleRound, RoundAccountKind::Pool] {
+            let (v1_space, new_len) = kind.spaces();
+            assert!(v1_space < new_len);
+        }
+        let round_id = 7u64;
+        assert_eq!(
+            RoundAccountKind::PresaleRound.address(round_id, &[]),
+            Pubkey::find_program_address(&[b"presale", &round_id.to_le_bytes()], &crate::ID).0
+        );
+    }
+
+    #[test]
+    fn migrated_round_stage_follows_its_records() {
+        assert!(pipeline_stage(false, false, false, false, false) == RoundStage::Presale);
+        assert!(pipeline_stage(true, false, false, false, false) == RoundStage::Lottery);
+        assert!(pipeline_stage(true, true, false, false, false) == RoundStage::TokenCreated);
+        assert!(pipeline_stage(true, true, true, false, false) == RoundStage::PoolLive);
+        assert!(pipeline_stage(true, true, true, true, false) == RoundStage::Exploded);
+        assert!(pipeline_stage(true, true, true, true, true) == RoundStage::Settled);
+    }
+
+    // ---------- quote mints ----------
+
+    fn token_2022_mint_data(transfer_fee: bool) -> Vec<u8> {
+        use spl_token_2022::extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensionsMut, ExtensionType,
+            StateWithExtensionsMut,
+        };
+        use spl_token_2022::state::Mint as TokenMint;
+
+        let extensions: &[ExtensionType] = if transfer_fee {
+            &[ExtensionType::TransferFeeConfig]
+        } else {
+            &[]
+        };
+        let len = ExtensionType::try_calculate_account_len::<TokenMint>(extensions).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state = StateWithExtensionsMut::<TokenMint>::unpack_uninitialized(&mut data).unwrap();
+        if transfer_fee {
+            state.init_extension::<TransferFeeConfig>(true).unwrap();
+        }
+        state.base = TokenMint {
+            decimals: 6,
+            is_initialized: true,
+            ..TokenMint::default()
+        };
+        state.pack_base();
+        if transfer_fee {
+            state.init_account_type().unwrap();
+        }
+        data
+    }
+
+    fn has_transfer_fee(owner: Pubkey, mut data: Vec<u8>) -> bool {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        mint_has_transfer_fee(&info).unwrap()
+    }
+
+    #[test]
+    fn transfer_fee_quote_mints_are_detected() {
+        assert!(has_transfer_fee(Token2022::id(), token_2022_mint_data(true)));
+        assert!(!has_transfer_fee(Token2022::id(), token_2022_mint_data(false)));
+        assert!(!has_transfer_fee(Token::id(), token_2022_mint_data(false)));
+    }
+
+    // ---------- cap commitment ----------
+
+    #[test]
+    fn cap_commitment_depends_on_the_salt() {
+        let cap = 500 * UNIT;
+        let commitment = cap_commitment(cap, &[7u8; 32]);
+        assert_eq!(commitment, cap_commitment(cap, &[7u8; 32]));
+        assert_ne!(commitment, cap_commitment(cap, &[8u8; 32]));
+        assert_ne!(commitment, cap_commitment(cap + 1, &[7u8; 32]));
+        assert_ne!(commitment, hash(&cap.to_le_bytes()).to_bytes());
+    }
+
+    // ---------- time-lock puzzle ----------
+
+    #[test]
+    fn solved_puzzle_key_decrypts_the_cap() {
+        let cap = 750 * UNIT;
+        let key = (0..5).fold([3u8; 32], |key, _| hash(&key).to_bytes());
+        let mut mask = [0u8; 8];
+        mask.copy_from_slice(&key[..8]);
+        let ciphertext = cap ^ u64::from_le_bytes(mask);
+
+        assert_eq!(decrypt_cap_puzzle(ciphertext, &key), cap);
+        assert_ne!(decrypt_cap_puzzle(ciphertext, &[3u8; 32]), cap);
+    }
+
+    // ---------- randomness ----------
+
+    #[test]
+    fn derived_cap_stays_within_bounds() {
+        assert_eq!(derive_cap_from_randomness(&[0u8; 32], 100, 200).unwrap(), 100);
+        assert_eq!(derive_cap_from_randomness(&[0xff; 32], 100, 100).unwrap(), 100);
+        assert_eq!(derive_cap_from_randomness(&[0xff; 32], 0, u64::MAX).unwrap(), u64::MAX);
+        for seed in 0..=255u8 {
+            let cap = derive_cap_from_randomness(&hash(&[seed]).to_bytes(), 100, 200).unwrap();
+            assert!((100..=200).contains(&cap));
+        }
+        assert!(derive_cap_from_randomness(&[0u8; 32], 200, 100).is_err());
+    }
+
+    fn switchboard_randomness_data(seed_slot: u64, reveal_slot: u64, value: [u8; 32]) -> Vec<u8> {
+        let mut data = vec![0u8; 8 + 432];
+        data[..8].copy_from_slice(&hash(b"account:RandomnessAccountData").to_bytes()[..8]);
+        data[104..112].copy_from_slice(&seed_slot.to_le_bytes());
+        data[144..152].copy_from_slice(&reveal_slot.to_le_bytes());
+        data[152..184].copy_from_slice(&value);
+        data
+    }
+
+    #[test]
+    fn switchboard_value_counts_only_after_the_current_commit() {
+        let pending = read_switchboard_randomness(&switchboard_randomness_data(10, 0, [7u8; 32])).unwrap();
+        assert_eq!(pending.seed_slot, 10);
+        assert!(pending.value.is_none());
+
+        let stale = read_switchboard_randomness(&switchboard_randomness_data(10, 9, [7u8; 32])).unwrap();
+        assert!(stale.value.is_none());
+
+        let revealed = read_switchboard_randomness(&switchboard_randomness_data(10, 12, [7u8; 32])).unwrap();
+        assert_eq!(revealed.value, Some([7u8; 32]));
+
+        assert!(read_switchboard_randomness(&switchboard_randomness_data(0, 0, [0u8; 32])).is_err());
+        let mut foreign = switchboard_randomness_data(10, 12, [7u8; 32]);
+        foreign[0] ^= 1;
+        assert!(read_switchboard_randomness(&foreign).is_err());
+    }
+
+    fn read_mock_randomness(revealed: bool) -> Result<RandomnessReading> {
+        let mut data = Vec::new();
+        MockRandomness {
+            authority: Pubkey::default(),
+            commitment: hash(&[9u8; 32]).to_bytes(),
+            value: if revealed { [9u8; 32] } else { [0u8; 32] },
+            revealed,
+            bump: 0,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        read_randomness(&info)
+    }
+
+    #[cfg(feature = "mock-randomness")]
+    #[test]
+    fn mock_randomness_reads_the_revealed_value() {
+        assert!(read_mock_randomness(false).unwrap().value.is_none());
+        let reading = read_mock_randomness(true).unwrap();
+        assert_eq!(reading.seed_slot, 0);
+        assert_eq!(reading.value, Some([9u8; 32]));
+    }
+
+    #[cfg(not(feature = "mock-randomness"))]
+    #[test]
+    fn mock_randomness_is_rejected_without_the_feature() {
+        assert!(read_mock_randomness(true).is_err());
+    }
+
+    // ---------- pool pricing ----------
+
+    fn priced_pool(last_price: u64, twap_price: u64, twap_updated_at: i64) -> Pool {
+        Pool {
+            round_id: 1,
+            mint: Pubkey::default(),
+            token_vault: Pubkey::default(),
+            sol_vault: Pubkey::default(),
+            sol_reserve: 0,
+            token_reserve: 0,
+            fee_bps: 0,
+            total_volume: 0,
+            total_fees: 0,
+            bump: 0,
+            token_vault_bump: 0,
+            sol_vault_bump: 0,
+            mode: PoolMode::ConstantProduct,
+            curve: BondingCurveConfig::default(),
+            curve_supply: 0,
+            launch_protection: LaunchProtectionConfig::default(),
+            fee_schedule: FeeScheduleConfig::default(),
+            last_price,
+            recent_move_bps: 0,
+            quote_mint: Pubkey::default(),
+            quote_authority_bump: 0,
+            rent_payer: Pubkey::default(),
+            ath_price: 0,
+            holder_count: 0,
+            quote_fees: 0,
+            fees_carried_over: false,
+            twap_price,
+            twap_updated_at,
+            curve_seed: 0,
+        }
+    }
+
+    #[test]
+    fn price_move_decays_with_time_not_trade_count() {
+        let mut pool = priced_pool(0, 0, 1_000);
+        pool.recent_move_bps = 800;
+        // Any number of trades within the same half-life leave the move intact
+        assert_eq!(pool.decayed_move_bps(1_000), 800);
+        assert_eq!(pool.decayed_move_bps(1_000 + PRICE_MOVE_HALF_LIFE - 1), 800);
+        assert_eq!(pool.decayed_move_bps(1_000 + PRICE_MOVE_HALF_LIFE), 400);
+        assert_eq!(pool.decayed_move_bps(1_000 + 3 * PRICE_MOVE_HALF_LIFE), 100);
+        assert_eq!(pool.decayed_move_bps(1_000 + 100 * PRICE_MOVE_HALF_LIFE), 0);
+    }
+
+    #[test]
+    fn presale_seed_alone_does_not_graduate_the_curve() {
+        let mut pool = priced_pool(0, 0, 0);
+        pool.curve = BondingCurveConfig {
+            graduation_threshold: 1_000,
+            ..linear_curve(1_000, 0)
+        };
+        pool.sol_reserve = 5_000;
+        pool.curve_seed = 5_000;
+        assert!(!pool.curve_graduates());
+        pool.sol_reserve = 5_999;
+        assert!(!pool.curve_graduates());
+        pool.sol_reserve = 6_000;
+        assert!(pool.curve_graduates());
+    }
+
+    #[test]
+    fn time_weighted_price_ignores_same_second_moves() {
+        let pool = priced_pool(10_000, 1_000, 500);
+        assert_eq!(pool.time_weighted_price(500), 1_000);
+        assert_eq!(pool.time_weighted_price(500 + PRICE_TWAP_WINDOW / 3), 4_000);
+        assert_eq!(pool.time_weighted_price(500 + PRICE_TWAP_WINDOW), 10_000);
+        assert_eq!(pool.time_weighted_price(500 + 10 * PRICE_TWAP_WINDOW), 10_000);
+
+        let falling = priced_pool(1_000, 10_000, 500);
+        assert_eq!(falling.time_weighted_price(500 + PRICE_TWAP_WINDOW / 3), 7_000);
+    }
+
+    #[test]
+    fn market_cap_uses_the_time_weighted_price() {
+        // 2 lamports per whole token (time-weighted), 1_000 whole tokens
+        let pool = priced_pool(1_000_000, 2, 500);
+        assert_eq!(pool_market_cap(&pool, 1_000 * UNIT, 500).unwrap(), 2_000);
+    }
 }
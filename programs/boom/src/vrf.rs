@@ -582,11 +582,9 @@ pub fn is_lottery_winner(
     ticket_value < threshold
 }
 
-/// Calculate the derived cap from VRF result
-pub fn derive_cap_from_vrf(result: [u8; 32], min_cap: u64, max_cap: u64) -> u64 {
-    let cap_range = max_cap - min_cap;
-    let random_u64 = u64::from_le_bytes(result[0..8].try_into().unwrap());
-    min_cap + (random_u64 % cap_range)
+/// Calculate the derived cap from VRF result (same mapping as the presale VRF trigger)
+pub fn derive_cap_from_vrf(result: [u8; 32], min_cap: u64, max_cap: u64) -> Result<u64> {
+    crate::derive_cap_from_randomness(&result, min_cap, max_cap)
 }
 
 // ============================================================================
@@ -97,6 +97,16 @@ async function main() {
     [Buffer.from('mint_authority'), roundIdBuffer],
     BOOM_PROGRAM_ID
   );
+  const [roundTombstonePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('round_tombstone'), roundIdBuffer],
+    BOOM_PROGRAM_ID
+  );
+  const [protocolPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('protocol')],
+    BOOM_PROGRAM_ID
+  );
+  // Anchor reads the program id in an optional account slot as "not provided"
+  const NONE = BOOM_PROGRAM_ID;
 
   // ============ PHASE 1: START PRESALE ============
   console.log('📝 PHASE 1: Starting Presale Round', ROUND_ID);
@@ -106,10 +116,16 @@ async function main() {
   const lotterySpots = 10;
   const minDeposit = new BN(0.05 * LAMPORTS_PER_SOL);
   const maxDeposit = new BN(5 * LAMPORTS_PER_SOL);
+  const allocationMode = 0; // AllocationMode::FixedPerWinner
+  const raiseTarget = new BN(0);
+  const allocationCap = new BN(0);
   
   const startPresaleIx = new TransactionInstruction({
     keys: [
       { pubkey: presalePda, isSigner: false, isWritable: true },
+      { pubkey: roundTombstonePda, isSigner: false, isWritable: false },
+      { pubkey: NONE, isSigner: false, isWritable: false }, // quote_mint (native SOL round)
+      { pubkey: NONE, isSigner: false, isWritable: false }, // sequencer
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
@@ -121,6 +137,9 @@ async function main() {
       Buffer.from(new Uint32Array([lotterySpots]).buffer),
       minDeposit.toArrayLike(Buffer, 'le', 8),
       maxDeposit.toArrayLike(Buffer, 'le', 8),
+      Buffer.from([allocationMode]),
+      raiseTarget.toArrayLike(Buffer, 'le', 8),
+      allocationCap.toArrayLike(Buffer, 'le', 8),
     ]),
   });
 
@@ -149,12 +168,18 @@ async function main() {
       { pubkey: presalePda, isSigner: false, isWritable: true },
       { pubkey: depositPda, isSigner: false, isWritable: true },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+      { pubkey: NONE, isSigner: false, isWritable: false }, // quote_mint
+      { pubkey: NONE, isSigner: false, isWritable: false }, // quote_vault
+      { pubkey: NONE, isSigner: false, isWritable: false }, // depositor_quote_account
+      { pubkey: NONE, isSigner: false, isWritable: false }, // quote_token_program
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
     programId: BOOM_PROGRAM_ID,
     data: Buffer.concat([
       depositDisc,
       depositAmount.toArrayLike(Buffer, 'le', 8),
+      Buffer.from(new Uint32Array([0]).buffer), // empty whitelist proof
+      new BN(0).toArrayLike(Buffer, 'le', 8),   // wallet_max_deposit (no whitelist)
     ]),
   });
 
@@ -199,30 +224,6 @@ async function main() {
 
   await sleep(2000);
 
-  // Mark winner
-  console.log('📝 PHASE 3b: Marking winner');
-  const markWinnerDisc = getDiscriminator('global', 'mark_winner');
-  
-  const markWinnerIx = new TransactionInstruction({
-    keys: [
-      { pubkey: presalePda, isSigner: false, isWritable: false },
-      { pubkey: depositPda, isSigner: false, isWritable: true },
-      { pubkey: wallet.publicKey, isSigner: true, isWritable: false },
-    ],
-    programId: BOOM_PROGRAM_ID,
-    data: markWinnerDisc,
-  });
-
-  try {
-    const tx3b = new Transaction().add(markWinnerIx);
-    const sig3b = await sendAndConfirmTransaction(connection, tx3b, [wallet]);
-    console.log(`  ✅ Winner marked: ${sig3b}\n`);
-  } catch (e: any) {
-    console.log(`  ⚠️ Mark winner error: ${e.message}\n`);
-  }
-
-  await sleep(2000);
-
   // ============ PHASE 4: CREATE TOKEN ============
   console.log('📝 PHASE 4: Creating Token2022 with transfer hook');
   
@@ -271,12 +272,19 @@ async function main() {
   const registerTokenDisc = getDiscriminator('global', 'register_presale_token');
   const totalSupply = new BN(1_000_000_000).mul(new BN(10).pow(new BN(9))); // 1B tokens
   const tokensPerWinner = new BN(10_000_000).mul(new BN(10).pow(new BN(9))); // 10M tokens per winner
+  const launchPrice = new BN(1_000); // lamports per whole token
+  const [launchPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('launch'), mint.toBuffer()],
+    BOOM_PROGRAM_ID
+  );
 
   const registerTokenIx = new TransactionInstruction({
     keys: [
-      { pubkey: presalePda, isSigner: false, isWritable: false },
+      { pubkey: presalePda, isSigner: false, isWritable: true },
       { pubkey: presaleTokenPda, isSigner: false, isWritable: true },
       { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: launchPda, isSigner: false, isWritable: true },
+      { pubkey: protocolPda, isSigner: false, isWritable: true },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
@@ -286,6 +294,7 @@ async function main() {
       roundIdBuffer,
       totalSupply.toArrayLike(Buffer, 'le', 8),
       tokensPerWinner.toArrayLike(Buffer, 'le', 8),
+      launchPrice.toArrayLike(Buffer, 'le', 8),
     ]),
   });
 
@@ -299,12 +308,38 @@ async function main() {
 
   await sleep(2000);
 
+  // Mark winner - needs the registered token's launch price
+  console.log('📝 PHASE 4c: Marking winner');
+  const markWinnerDisc = getDiscriminator('global', 'mark_winner');
+  
+  const markWinnerIx = new TransactionInstruction({
+    keys: [
+      { pubkey: presalePda, isSigner: false, isWritable: true },
+      { pubkey: presaleTokenPda, isSigner: false, isWritable: false },
+      { pubkey: depositPda, isSigner: false, isWritable: true },
+      { pubkey: wallet.publicKey, isSigner: true, isWritable: false },
+    ],
+    programId: BOOM_PROGRAM_ID,
+    data: markWinnerDisc,
+  });
+
+  try {
+    const tx4c = new Transaction().add(markWinnerIx);
+    const sig4c = await sendAndConfirmTransaction(connection, tx4c, [wallet]);
+    console.log(`  ✅ Winner marked: ${sig4c}\n`);
+  } catch (e: any) {
+    console.log(`  ⚠️ Mark winner error: ${e.message}\n`);
+  }
+
+  await sleep(2000);
+
   // ============ PHASE 5: INIT EXPLOSION TRACKING ============
   console.log('📝 PHASE 5: Initializing explosion tracking');
   
   // Create a secret cap hash (e.g., cap at 1000 SOL market cap)
   const secretCap = new BN(1000 * LAMPORTS_PER_SOL);
-  const capHash = crypto.createHash('sha256').update(secretCap.toArrayLike(Buffer, 'le', 8)).digest();
+  const capSalt = crypto.randomBytes(32); // keep this to reveal the cap later
+  const capHash = crypto.createHash('sha256').update(Buffer.concat([secretCap.toArrayLike(Buffer, 'le', 8), capSalt])).digest();
   
   const initExplosionDisc = getDiscriminator('global', 'init_presale_explosion');
   
@@ -337,6 +372,18 @@ async function main() {
   console.log('📝 PHASE 6: Creating AMM pool');
   
   const feeBps = 100; // 1% fee
+  // LaunchProtectionConfig: 60s window, 1 SOL per wallet, no slot cooldown
+  const protectionDuration = new BN(60);
+  const protectionMaxBuy = new BN(1 * LAMPORTS_PER_SOL);
+  const protectionMinSlots = new BN(0);
+  // FeeScheduleConfig: zero values disable each component (max_fee_bps 0 = 10% cap)
+  const finalWindowSeconds = new BN(0);
+  const feeSchedule = new Uint16Array([
+    0, // final_window_buy_fee_bps
+    0, // final_window_sell_fee_bps
+    0, // volatility_multiplier_bps
+    0, // max_fee_bps
+  ]);
   const createPoolDisc = getDiscriminator('global', 'create_pool');
   
   const createPoolIx = new TransactionInstruction({
@@ -345,9 +392,15 @@ async function main() {
       { pubkey: presaleTokenPda, isSigner: false, isWritable: false },
       { pubkey: poolPda, isSigner: false, isWritable: true },
       { pubkey: mint, isSigner: false, isWritable: false },  // mint comes BEFORE vaults
+      { pubkey: launchPda, isSigner: false, isWritable: true },
       { pubkey: tokenVaultPda, isSigner: false, isWritable: true },
       { pubkey: solVaultPda, isSigner: false, isWritable: true },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+      { pubkey: NONE, isSigner: false, isWritable: false }, // quote_mint
+      { pubkey: NONE, isSigner: false, isWritable: false }, // presale_quote_vault
+      { pubkey: NONE, isSigner: false, isWritable: false }, // quote_authority
+      { pubkey: NONE, isSigner: false, isWritable: false }, // pool_quote_vault
+      { pubkey: NONE, isSigner: false, isWritable: false }, // quote_token_program
       { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },  // token_program before system
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
@@ -356,6 +409,11 @@ async function main() {
       createPoolDisc,
       roundIdBuffer,
       Buffer.from(new Uint16Array([feeBps]).buffer),
+      protectionDuration.toArrayLike(Buffer, 'le', 8),
+      protectionMaxBuy.toArrayLike(Buffer, 'le', 8),
+      protectionMinSlots.toArrayLike(Buffer, 'le', 8),
+      finalWindowSeconds.toArrayLike(Buffer, 'le', 8),
+      Buffer.from(feeSchedule.buffer),
     ]),
   });
 
@@ -376,7 +434,7 @@ async function main() {
   
   const registerLpIx = new TransactionInstruction({
     keys: [
-      { pubkey: presalePda, isSigner: false, isWritable: false },
+      { pubkey: presalePda, isSigner: false, isWritable: true },
       { pubkey: lpInfoPda, isSigner: false, isWritable: true },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
@@ -411,6 +469,7 @@ async function main() {
   const startTimerIx = new TransactionInstruction({
     keys: [
       { pubkey: explosionPda, isSigner: false, isWritable: true },
+      { pubkey: presalePda, isSigner: false, isWritable: false },
       { pubkey: lpInfoPda, isSigner: false, isWritable: false },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: false },
     ],
@@ -434,8 +493,9 @@ async function main() {
   console.log('✅ Presale started');
   console.log('✅ Deposit made'); 
   console.log('✅ Presale ended + lottery');
-  console.log('✅ Winner marked');
   console.log('✅ Token created');
+  console.log('✅ Token registered');
+  console.log('✅ Winner marked');
   console.log('✅ Explosion tracking initialized');
   console.log('✅ Pool created');
   console.log('✅ LP registered');
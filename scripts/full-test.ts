@@ -9,7 +9,7 @@ import {
   sendAndConfirmTransaction
 } from '@solana/web3.js';
 import * as fs from 'fs';
-import { createHash } from 'crypto';
+import { createHash, randomBytes } from 'crypto';
 
 const PROGRAM_ID = new PublicKey('GC56De2SrwjGsCCFimwqxzxwjpHBEsubP3AV1yXwVtrn');
 const TROY_PUBKEY = new PublicKey('6FeR4ioSTQBV78mvwCJLaGVGnfXGx9AqqNS2bKRJkNQQ'); // Troy's wallet
@@ -134,7 +134,8 @@ async function main() {
   console.log('\nStep 3: Initializing explosion (5-min time limit)...');
   const deadline = BigInt(Math.floor(Date.now() / 1000) + 5 * 60); // 5 minutes from now
   const secretCap = BigInt(1_000_000 * LAMPORTS_PER_SOL); // 1M SOL (won't hit, time limit will trigger)
-  const capHash = createHash('sha256').update(Buffer.from(encodeU64(secretCap))).digest();
+  const capSalt = randomBytes(32); // keep this to reveal the cap later
+  const capHash = createHash('sha256').update(Buffer.concat([Buffer.from(encodeU64(secretCap)), capSalt])).digest();
 
   try {
     const initExplosionData = Buffer.concat([